@@ -0,0 +1,301 @@
+//! Radiance RGBE images: a mantissa byte per channel sharing one exponent byte,
+//! with scanlines optionally run-length encoded one channel at a time.
+
+use std::io::{self, Read, Write};
+
+use super::{ppm::invalid, Canvas};
+use crate::{color::Color, error::Result};
+
+/// Largest channel value an RGBE pixel can hold
+const MAX_VALUE: f64 = 255. / 256. * (1u128 << 127) as f64;
+/// Runs shorter than this are cheaper to store as literals
+const MIN_RUN: usize = 4;
+/// Scanlines outside this width range cannot use the run-length encoding
+const RLE_WIDTHS: std::ops::Range<usize> = 8..0x8000;
+
+impl Canvas {
+	/// Radiance RGBE image, see `write_hdr`
+	pub fn as_hdr(&self) -> Vec<u8> {
+		let mut buf = Vec::new();
+		self.write_hdr(&mut buf)
+			.expect("writing to a Vec cannot fail");
+		buf
+	}
+
+	/// Stream the canvas as a run-length encoded Radiance RGBE image.
+	/// Channels are kept unbounded above, only negative values and NaN become 0.
+	pub fn write_hdr<W: Write>(&self, mut w: W) -> io::Result<()> {
+		write!(
+			w,
+			"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {} +X {}\n",
+			self.height, self.width
+		)?;
+
+		let mut buf = Vec::with_capacity(self.width * 4);
+		let mut channels: [Vec<u8>; 4] = Default::default();
+		for row in self.iter_rows() {
+			buf.clear();
+			if RLE_WIDTHS.contains(&self.width) {
+				channels.iter_mut().for_each(Vec::clear);
+				for pixel in row {
+					for (channel, byte) in channels.iter_mut().zip(&rgbe(pixel)) {
+						channel.push(*byte);
+					}
+				}
+				buf.extend_from_slice(&[2, 2, (self.width >> 8) as u8, self.width as u8]);
+				for channel in &channels {
+					write_rle(channel, &mut buf);
+				}
+			} else {
+				for pixel in row {
+					buf.extend_from_slice(&rgbe(pixel));
+				}
+			}
+			w.write_all(&buf)?;
+		}
+		Ok(())
+	}
+
+	/// Decode a Radiance RGBE image with flat, old-style or new-style run-length
+	/// encoded scanlines. Only the standard `-Y h +X w` and `+Y h +X w`
+	/// orientations are supported, and `EXPOSURE` lines are ignored.
+	pub fn read_hdr<R: Read>(mut r: R) -> Result<Canvas> {
+		let mut data = Vec::new();
+		r.read_to_end(&mut data)?;
+		Canvas::from_hdr(&data)
+	}
+
+	/// Decode Radiance RGBE data held in memory, see `read_hdr`
+	pub fn from_hdr(data: &[u8]) -> Result<Canvas> {
+		let mut lines = HeaderLines { data, pos: 0 };
+		if !lines.next()?.starts_with("#?") {
+			return Err(invalid("expected a #? signature line"));
+		}
+		loop {
+			let line = lines.next()?;
+			if line.is_empty() {
+				break;
+			}
+			if let Some(format) = line.strip_prefix("FORMAT=") {
+				if format.trim() != "32-bit_rle_rgbe" {
+					return Err(invalid(format!("unsupported pixel format {}", format)));
+				}
+			}
+		}
+
+		let resolution = lines.next()?;
+		let (flip, height, width) = match resolution.split_whitespace().collect::<Vec<_>>()[..] {
+			[y @ ("-Y" | "+Y"), height, "+X", width] => (
+				y == "+Y",
+				height.parse::<usize>().ok(),
+				width.parse::<usize>().ok(),
+			),
+			_ => (false, None, None),
+		};
+		let (width, height) = width
+			.zip(height)
+			.ok_or_else(|| invalid(format!("unsupported resolution line {}", resolution)))?;
+		if (width == 0) != (height == 0) {
+			return Err(invalid(format!("{}x{} image has no pixels", width, height)));
+		}
+		let size = width
+			.checked_mul(height)
+			.ok_or_else(|| invalid("image dimensions are too large"))?;
+
+		let mut scanlines = Scanlines {
+			data,
+			pos: lines.pos,
+			width,
+		};
+		let mut pixels = Vec::with_capacity(size.min(data.len()));
+		let mut scanline = Vec::with_capacity(width);
+		for _ in 0..height {
+			scanlines.read(&mut scanline)?;
+			pixels.extend(scanline.iter().map(color));
+		}
+		if flip {
+			// Bottom-to-top images are stored with their last row first
+			let rows: Vec<_> = pixels
+				.chunks(width.max(1))
+				.rev()
+				.flatten()
+				.copied()
+				.collect();
+			pixels = rows;
+		}
+
+		Ok(Canvas {
+			width,
+			height,
+			pixels,
+		})
+	}
+}
+
+/// Share the exponent of the brightest channel between all three
+fn rgbe(color: &Color) -> [u8; 4] {
+	let [r, g, b] = [color.r, color.g, color.b].map(|c| if c > 0. { c.min(MAX_VALUE) } else { 0. });
+	let brightest = r.max(g).max(b);
+	if brightest < 1e-32 {
+		return [0; 4];
+	}
+	// brightest = mantissa * 2^exponent with mantissa in [0.5, 1)
+	let mut exponent = brightest.log2().floor() as i32 + 1;
+	let mut mantissa = brightest / 2f64.powi(exponent);
+	if mantissa >= 1. {
+		exponent += 1;
+		mantissa /= 2.;
+	} else if mantissa < 0.5 {
+		exponent -= 1;
+		mantissa *= 2.;
+	}
+	let scale = mantissa * 256. / brightest;
+	[
+		(r * scale) as u8,
+		(g * scale) as u8,
+		(b * scale) as u8,
+		(exponent + 128) as u8,
+	]
+}
+
+/// The center of the range of colors a pixel stands for
+fn color(rgbe: &[u8; 4]) -> Color {
+	if rgbe[3] == 0 {
+		return Color::new(0., 0., 0.);
+	}
+	let scale = 2f64.powi(i32::from(rgbe[3]) - (128 + 8));
+	let channel = |byte: u8| (f64::from(byte) + 0.5) * scale;
+	Color::new(channel(rgbe[0]), channel(rgbe[1]), channel(rgbe[2]))
+}
+
+/// Append runs of `MIN_RUN` or more equal bytes as `128 + length, byte` and
+/// everything between them as `length, bytes...`
+fn write_rle(channel: &[u8], out: &mut Vec<u8>) {
+	let run_at = |start: usize| {
+		channel[start..]
+			.iter()
+			.take(127)
+			.take_while(|&&byte| byte == channel[start])
+			.count()
+	};
+
+	let mut i = 0;
+	while i < channel.len() {
+		let (mut start, mut run) = (i, 0);
+		while start < channel.len() {
+			run = run_at(start);
+			if run >= MIN_RUN {
+				break;
+			}
+			// No longer run can begin inside a shorter one
+			start += run;
+			run = 0;
+		}
+		for literals in channel[i..start].chunks(128) {
+			out.push(literals.len() as u8);
+			out.extend_from_slice(literals);
+		}
+		if run > 0 {
+			out.extend_from_slice(&[128 + run as u8, channel[start]]);
+		}
+		i = start + run;
+	}
+}
+
+/// Newline terminated text lines of the header
+struct HeaderLines<'a> {
+	data: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> HeaderLines<'a> {
+	fn next(&mut self) -> Result<&'a str> {
+		let rest = &self.data[self.pos..];
+		let end = rest
+			.iter()
+			.position(|&b| b == b'\n')
+			.ok_or_else(|| invalid("header is truncated"))?;
+		self.pos += end + 1;
+		std::str::from_utf8(&rest[..end])
+			.map(|line| line.trim_end_matches('\r'))
+			.map_err(|_| invalid("header is not valid text"))
+	}
+}
+
+/// Decodes the raster one scanline at a time
+struct Scanlines<'a> {
+	data: &'a [u8],
+	pos: usize,
+	width: usize,
+}
+
+impl Scanlines<'_> {
+	fn byte(&mut self) -> Result<u8> {
+		let byte = *self
+			.data
+			.get(self.pos)
+			.ok_or_else(|| invalid("raster is truncated"))?;
+		self.pos += 1;
+		Ok(byte)
+	}
+
+	fn pixel(&mut self) -> Result<[u8; 4]> {
+		Ok([self.byte()?, self.byte()?, self.byte()?, self.byte()?])
+	}
+
+	fn read(&mut self, scanline: &mut Vec<[u8; 4]>) -> Result<()> {
+		scanline.clear();
+		let width = self.width;
+		let rest = &self.data[self.pos..];
+		if RLE_WIDTHS.contains(&width)
+			&& rest.len() >= 4
+			&& rest[..2] == [2, 2]
+			&& usize::from(rest[2]) << 8 | usize::from(rest[3]) == width
+		{
+			self.pos += 4;
+			scanline.resize(width, [0; 4]);
+			for channel in 0..4 {
+				let mut x = 0;
+				while x < width {
+					let count = self.byte()?;
+					let (length, run) = match count {
+						0 => return Err(invalid("zero length run")),
+						129..=255 => (usize::from(count - 128), true),
+						_ => (usize::from(count), false),
+					};
+					if x + length > width {
+						return Err(invalid("run overruns the scanline"));
+					}
+					let value = if run { self.byte()? } else { 0 };
+					for pixel in &mut scanline[x..x + length] {
+						pixel[channel] = if run { value } else { self.byte()? };
+					}
+					x += length;
+				}
+			}
+			return Ok(());
+		}
+
+		// Flat pixels, where `1, 1, 1, n` repeats the previous pixel and
+		// consecutive repeats contribute ever higher bytes of the count
+		let mut shift = 0;
+		while scanline.len() < width {
+			let pixel = self.pixel()?;
+			if pixel[..3] == [1, 1, 1] {
+				let previous = *scanline
+					.last()
+					.ok_or_else(|| invalid("repeat at the start of a scanline"))?;
+				let count = usize::from(pixel[3])
+					.checked_shl(shift)
+					.filter(|&count| scanline.len() + count <= width)
+					.ok_or_else(|| invalid("run overruns the scanline"))?;
+				scanline.resize(scanline.len() + count, previous);
+				shift += 8;
+			} else {
+				scanline.push(pixel);
+				shift = 0;
+			}
+		}
+		Ok(())
+	}
+}
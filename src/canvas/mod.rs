@@ -0,0 +1,189 @@
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{
+	fs::{self, File},
+	io::{self, BufWriter, Write},
+	ops::{Index, IndexMut},
+	path::Path,
+};
+
+use crate::{
+	color::{Color, BLACK},
+	error::{Error, Result},
+	utils::clamp_and_normalize,
+};
+
+mod hdr;
+mod pfm;
+mod png;
+mod ppm;
+mod zlib;
+
+/// Number of bits used to store each color channel of an encoded image
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BitDepth {
+	Eight,
+	Sixteen,
+}
+
+impl BitDepth {
+	/// Largest sample value, i.e. the value of a fully saturated channel
+	pub fn max_value(self) -> usize {
+		match self {
+			BitDepth::Eight => 255,
+			BitDepth::Sixteen => 65535,
+		}
+	}
+
+	/// Clamp a channel to 0.0..=1.0 and append it as a big-endian sample
+	pub fn push_sample(self, value: f64, buf: &mut Vec<u8>) {
+		let sample = clamp_and_normalize(value, self.max_value());
+		match self {
+			BitDepth::Eight => buf.push(sample as u8),
+			BitDepth::Sixteen => buf.extend_from_slice(&(sample as u16).to_be_bytes()),
+		}
+	}
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Canvas {
+	pub width: usize,
+	pub height: usize,
+	pub pixels: Vec<Color>,
+}
+
+impl Canvas {
+	pub fn new(width: usize, height: usize) -> Self {
+		let pixels = vec![BLACK; width * height];
+		Self {
+			width,
+			height,
+			pixels,
+		}
+	}
+
+	fn to_row_major(&self, i: usize, j: usize) -> usize {
+		self.width * i + j
+	}
+
+	pub fn write_pixel(&mut self, x: usize, y: usize, pixel: Color) -> Result<()> {
+		if x < self.width && y < self.height {
+			let i = self.to_row_major(y, x);
+			self.pixels[i] = pixel;
+			Ok(())
+		} else {
+			Err(Error::OutOfBounds {
+				x,
+				y,
+				width: self.width,
+				height: self.height,
+			})
+		}
+	}
+
+	/// Encode the canvas into the file at `path`, choosing the format from its extension:
+	/// `.png` is written as 8-bit PNG, `.ppm` as plain PPM (P3) just like `write_ppm`,
+	/// and `.hdr` and `.pfm` keep the full dynamic range as Radiance RGBE and Portable Float Map.
+	/// For the much smaller binary PPM call `write_p6` with a file of your own.
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+		let path = path.as_ref();
+		let extension = path
+			.extension()
+			.and_then(|ext| ext.to_str())
+			.map(str::to_ascii_lowercase)
+			.unwrap_or_default();
+
+		let encode: fn(&Canvas, &mut dyn Write) -> io::Result<()> = match extension.as_str() {
+			"hdr" => |canvas, w| canvas.write_hdr(w),
+			"pfm" => |canvas, w| canvas.write_pfm(w),
+			"png" => |canvas, w| canvas.write_png(w, BitDepth::Eight),
+			"ppm" => |canvas, w| canvas.write_ppm(w),
+			_ => return Err(Error::UnsupportedFormat(extension)),
+		};
+
+		let mut w = BufWriter::new(File::create(path)?);
+		encode(self, &mut w)?;
+		w.flush()?;
+		Ok(())
+	}
+
+	/// Decode the image at `path`, recognizing Radiance RGBE and Portable Float Map
+	/// by their signature and reading anything else as PPM or PGM, see `read_ppm`
+	pub fn load<P: AsRef<Path>>(path: P) -> Result<Canvas> {
+		let data = fs::read(path)?;
+		match data.get(..2) {
+			Some(b"#?") => Canvas::from_hdr(&data),
+			Some(b"PF") | Some(b"Pf") => Canvas::from_pfm(&data),
+			_ => Canvas::from_ppm(&data),
+		}
+	}
+
+	/// Iterate over all elements
+	pub fn iter(&self) -> impl Iterator<Item = &Color> {
+		self.pixels.iter()
+	}
+
+	/// Iterate over the ith row of the Canvas
+	pub fn iter_row(&self, i: usize) -> impl Iterator<Item = &Color> {
+		self.iter().skip(i * self.width).take(self.width)
+	}
+
+	/// Iterate over the jth coloumn of the Canvas
+	pub fn iter_col(&self, j: usize) -> impl Iterator<Item = &Color> {
+		self.iter().skip(j).step_by(self.width)
+	}
+
+	/// Iterate over all rows of the Canvas
+	pub fn iter_rows(&self) -> impl Iterator<Item = impl Iterator<Item = &Color>> {
+		(0..self.height).map(move |i| self.iter_row(i))
+	}
+}
+
+/// Rejects canvases whose pixel count doesn't match their size
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Canvas {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		struct Fields {
+			width: usize,
+			height: usize,
+			pixels: Vec<Color>,
+		}
+
+		let Fields {
+			width,
+			height,
+			pixels,
+		} = Fields::deserialize(deserializer)?;
+		let size = width.checked_mul(height).ok_or_else(|| {
+			de::Error::custom(format!("a {}x{} canvas is too large", width, height))
+		})?;
+		if pixels.len() != size {
+			let expected = format!("{} pixels for a {}x{} canvas", size, width, height);
+			return Err(de::Error::invalid_length(pixels.len(), &expected.as_str()));
+		}
+		Ok(Canvas {
+			width,
+			height,
+			pixels,
+		})
+	}
+}
+
+impl Index<(usize, usize)> for Canvas {
+	type Output = Color;
+
+	fn index(&self, coords: (usize, usize)) -> &Self::Output {
+		let (i, j) = coords;
+		&self.pixels[self.to_row_major(i, j)]
+	}
+}
+
+impl IndexMut<(usize, usize)> for Canvas {
+	fn index_mut(&mut self, coords: (usize, usize)) -> &mut Self::Output {
+		let (i, j) = coords;
+		let idx = self.to_row_major(i, j);
+		&mut self.pixels[idx]
+	}
+}
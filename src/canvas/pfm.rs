@@ -0,0 +1,107 @@
+use std::{
+	convert::TryInto,
+	io::{self, Read, Write},
+};
+
+use super::{
+	ppm::{invalid, Parser},
+	Canvas,
+};
+use crate::{color::Color, error::Result};
+
+impl Canvas {
+	/// Portable Float Map, see `write_pfm`
+	pub fn as_pfm(&self) -> Vec<u8> {
+		let mut buf = Vec::new();
+		self.write_pfm(&mut buf)
+			.expect("writing to a Vec cannot fail");
+		buf
+	}
+
+	/// Stream the canvas as a color Portable Float Map.
+	/// Channels are stored unclamped as little-endian `f32`, bottom row first.
+	pub fn write_pfm<W: Write>(&self, mut w: W) -> io::Result<()> {
+		// A negative scale marks the samples as little-endian
+		write!(w, "PF\n{} {}\n-1.0\n", self.width, self.height)?;
+
+		let mut buf = Vec::with_capacity(self.width * 3 * 4);
+		for y in (0..self.height).rev() {
+			for pixel in self.iter_row(y) {
+				for &channel in &[pixel.r, pixel.g, pixel.b] {
+					buf.extend_from_slice(&(channel as f32).to_le_bytes());
+				}
+			}
+			w.write_all(&buf)?;
+			buf.clear();
+		}
+		Ok(())
+	}
+
+	/// Decode a color (`PF`) or grayscale (`Pf`) Portable Float Map.
+	/// Samples are taken as they are; the magnitude of the scale factor is ignored.
+	pub fn read_pfm<R: Read>(mut r: R) -> Result<Canvas> {
+		let mut data = Vec::new();
+		r.read_to_end(&mut data)?;
+		Canvas::from_pfm(&data)
+	}
+
+	/// Decode Portable Float Map data held in memory, see `read_pfm`
+	pub fn from_pfm(data: &[u8]) -> Result<Canvas> {
+		let channels = match data {
+			[b'P', b'F', ..] => 3,
+			[b'P', b'f', ..] => 1,
+			_ => return Err(invalid("expected magic number PF or Pf")),
+		};
+		let mut parser = Parser { data, pos: 2 };
+		let width = parser.number("width")?;
+		let height = parser.number("height")?;
+		if (width == 0) != (height == 0) {
+			return Err(invalid(format!("{}x{} image has no pixels", width, height)));
+		}
+		let scale = parser.float("scale")?;
+		if scale == 0. || !scale.is_finite() {
+			return Err(invalid(format!("scale {} is not a non-zero number", scale)));
+		}
+		let little_endian = scale < 0.;
+
+		if !data.get(parser.pos).is_some_and(u8::is_ascii_whitespace) {
+			return Err(invalid("expected whitespace after scale"));
+		}
+		let (row_size, raster_size) = width
+			.checked_mul(channels * 4)
+			.and_then(|row| Some((row, row.checked_mul(height)?)))
+			.ok_or_else(|| invalid("image dimensions are too large"))?;
+		let raster = data
+			.get(parser.pos + 1..)
+			.filter(|raster| raster.len() >= raster_size)
+			.ok_or_else(|| invalid("raster is truncated"))?;
+
+		let sample = |bytes: &[u8]| {
+			let bytes = bytes.try_into().unwrap();
+			f64::from(if little_endian {
+				f32::from_le_bytes(bytes)
+			} else {
+				f32::from_be_bytes(bytes)
+			})
+		};
+		let mut pixels = Vec::with_capacity(width * height);
+		for y in (0..height).rev() {
+			let row = &raster[y * row_size..(y + 1) * row_size];
+			pixels.extend(row.chunks(channels * 4).map(|pixel| {
+				let mut channels = pixel.chunks(4).map(sample);
+				let r = channels.next().unwrap();
+				Color::new(
+					r,
+					channels.next().unwrap_or(r),
+					channels.next().unwrap_or(r),
+				)
+			}));
+		}
+
+		Ok(Canvas {
+			width,
+			height,
+			pixels,
+		})
+	}
+}
@@ -0,0 +1,154 @@
+use std::{
+	convert::TryFrom,
+	io::{self, Write},
+};
+
+use super::{zlib, BitDepth, Canvas};
+use crate::error::Result;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+/// PNG color type for RGB samples without alpha
+const TRUECOLOR: u8 = 2;
+
+impl Canvas {
+	/// Truecolor PNG with 8 or 16 bits per channel.
+	/// Fails for an empty canvas, which PNG cannot represent.
+	pub fn as_png(&self, depth: BitDepth) -> Result<Vec<u8>> {
+		let mut buf = Vec::new();
+		self.write_png(&mut buf, depth)?;
+		Ok(buf)
+	}
+
+	/// Write the canvas as a truecolor PNG with 8 or 16 bits per channel.
+	/// Fails with `InvalidInput` for an empty canvas, which PNG cannot represent.
+	pub fn write_png<W: Write>(&self, mut w: W, depth: BitDepth) -> io::Result<()> {
+		let dimension = |n: usize| {
+			u32::try_from(n)
+				.ok()
+				.filter(|&n| n > 0 && n <= i32::MAX as u32)
+				.ok_or_else(|| {
+					io::Error::new(
+						io::ErrorKind::InvalidInput,
+						format!("PNG dimensions must be within 1..=2^31-1, not {}", n),
+					)
+				})
+		};
+		let (width, height) = (dimension(self.width)?, dimension(self.height)?);
+		let bits = match depth {
+			BitDepth::Eight => 8,
+			BitDepth::Sixteen => 16,
+		};
+
+		let mut header = Vec::with_capacity(13);
+		header.extend_from_slice(&width.to_be_bytes());
+		header.extend_from_slice(&height.to_be_bytes());
+		// Bit depth, color type, deflate compression, adaptive filtering, no interlace
+		header.extend_from_slice(&[bits, TRUECOLOR, 0, 0, 0]);
+
+		// Every scanline is stored filtered and prefixed with its filter type
+		let bytes_per_pixel = 3 * usize::from(bits / 8);
+		let stride = self.width * bytes_per_pixel;
+		let mut raw = Vec::with_capacity(self.height * (1 + stride));
+		let mut previous = vec![0; stride];
+		let mut current = Vec::with_capacity(stride);
+		for row in self.iter_rows() {
+			current.clear();
+			for pixel in row {
+				depth.push_sample(pixel.r, &mut current);
+				depth.push_sample(pixel.g, &mut current);
+				depth.push_sample(pixel.b, &mut current);
+			}
+			filter_scanline(&current, &previous, bytes_per_pixel, &mut raw);
+			std::mem::swap(&mut current, &mut previous);
+		}
+
+		w.write_all(&SIGNATURE)?;
+		write_chunk(&mut w, b"IHDR", &header)?;
+		write_chunk(&mut w, b"IDAT", &zlib::compress(&raw))?;
+		write_chunk(&mut w, b"IEND", &[])
+	}
+}
+
+/// Append the filter type and filtered bytes of the filter that is likely to
+/// compress best, judged by the smallest sum of the bytes taken as signed values
+fn filter_scanline(line: &[u8], previous: &[u8], bpp: usize, out: &mut Vec<u8>) {
+	let left = |i: usize| if i >= bpp { line[i - bpp] } else { 0 };
+	let upper_left = |i: usize| if i >= bpp { previous[i - bpp] } else { 0 };
+	let filter = |kind: u8, i: usize| -> u8 {
+		let (a, b, c) = (left(i), previous[i], upper_left(i));
+		line[i].wrapping_sub(match kind {
+			0 => 0,
+			1 => a,
+			2 => b,
+			3 => ((u16::from(a) + u16::from(b)) / 2) as u8,
+			_ => paeth(a, b, c),
+		})
+	};
+
+	let cost = |kind: u8| -> u64 {
+		(0..line.len())
+			.map(|i| u64::from((filter(kind, i) as i8).unsigned_abs()))
+			.sum()
+	};
+	let best = (0..5).min_by_key(|&kind| cost(kind)).unwrap_or(0);
+
+	out.push(best);
+	out.extend((0..line.len()).map(|i| filter(best, i)));
+}
+
+/// Whichever of left, up or upper left is closest to `a + b - c`
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+	let p = i16::from(a) + i16::from(b) - i16::from(c);
+	let (pa, pb, pc) = (
+		(p - i16::from(a)).abs(),
+		(p - i16::from(b)).abs(),
+		(p - i16::from(c)).abs(),
+	);
+	if pa <= pb && pa <= pc {
+		a
+	} else if pb <= pc {
+		b
+	} else {
+		c
+	}
+}
+
+/// Length, type, data and the CRC of type and data
+fn write_chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+	let length = u32::try_from(data.len())
+		.map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "PNG chunk is too large"))?;
+	w.write_all(&length.to_be_bytes())?;
+	w.write_all(kind)?;
+	w.write_all(data)?;
+	let crc = !crc32_update(crc32_update(!0, kind), data);
+	w.write_all(&crc.to_be_bytes())
+}
+
+/// Feed `data` into a running CRC-32, the checksum PNG uses for chunks
+fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+	data.iter().fold(crc, |crc, &byte| {
+		CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+	})
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+	let mut table = [0; 256];
+	let mut n = 0;
+	while n < 256 {
+		let mut c = n as u32;
+		let mut k = 0;
+		while k < 8 {
+			c = if c & 1 == 1 {
+				0xedb8_8320 ^ (c >> 1)
+			} else {
+				c >> 1
+			};
+			k += 1;
+		}
+		table[n] = c;
+		n += 1;
+	}
+	table
+}
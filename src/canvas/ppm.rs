@@ -0,0 +1,241 @@
+use std::io::{self, Read, Write};
+
+use super::{BitDepth, Canvas};
+use crate::{
+	color::Color,
+	error::{Error, Result},
+	utils::clamp_and_normalize,
+};
+
+/// Plain PPM lines must not be longer than this
+const MAX_LINE_LENGTH: usize = 70;
+
+impl Canvas {
+	/// Plain (P3) PPM with 8-bit samples
+	pub fn as_ppm(&self) -> String {
+		self.as_p3(BitDepth::Eight)
+	}
+
+	/// Plain (P3) PPM, writing every sample as ASCII decimal
+	pub fn as_p3(&self, depth: BitDepth) -> String {
+		let mut buf = Vec::new();
+		self.write_p3(&mut buf, depth)
+			.expect("writing to a Vec cannot fail");
+		String::from_utf8(buf).expect("PPM output is ASCII")
+	}
+
+	/// Raw (P6) PPM, writing every sample as one byte or two big-endian bytes
+	pub fn as_p6(&self, depth: BitDepth) -> Vec<u8> {
+		let mut buf = Vec::new();
+		self.write_p6(&mut buf, depth)
+			.expect("writing to a Vec cannot fail");
+		buf
+	}
+
+	/// Decode a plain or raw PPM (P3, P6) or PGM (P2, P5) image.
+	/// Samples are scaled by the image's maxval into 0.0..=1.0; gray levels become equal RGB channels.
+	pub fn read_ppm<R: Read>(mut r: R) -> Result<Canvas> {
+		let mut data = Vec::new();
+		r.read_to_end(&mut data)?;
+		Canvas::from_ppm(&data)
+	}
+
+	/// Decode PPM or PGM image data held in memory, see `read_ppm`
+	pub fn from_ppm(data: &[u8]) -> Result<Canvas> {
+		let mut parser = Parser { data, pos: 0 };
+		let (channels, binary) = match data {
+			[b'P', b'2', ..] => (1, false),
+			[b'P', b'3', ..] => (3, false),
+			[b'P', b'5', ..] => (1, true),
+			[b'P', b'6', ..] => (3, true),
+			_ => return Err(invalid("expected magic number P2, P3, P5 or P6")),
+		};
+		parser.pos = 2;
+
+		let width = parser.number("width")?;
+		let height = parser.number("height")?;
+		let maxval = parser.number("maxval")?;
+		if maxval == 0 || maxval > 65535 {
+			return Err(invalid(format!(
+				"maxval {} is not within 1..=65535",
+				maxval
+			)));
+		}
+		let count = width
+			.checked_mul(height)
+			.and_then(|pixels| pixels.checked_mul(channels))
+			.ok_or_else(|| invalid("image dimensions are too large"))?;
+
+		let mut samples = Vec::with_capacity(count.min(data.len()));
+		if binary {
+			// A single whitespace character separates the header from the raster
+			if !data.get(parser.pos).is_some_and(u8::is_ascii_whitespace) {
+				return Err(invalid("expected whitespace after maxval"));
+			}
+			let start = parser.pos + 1;
+			let size = if maxval > 255 { 2 } else { 1 };
+			let raster = data
+				.get(start..)
+				.filter(|raster| raster.len() / size >= count)
+				.ok_or_else(|| invalid("raster is truncated"))?;
+			samples.extend(raster.chunks(size).take(count).map(|bytes| match *bytes {
+				[hi, lo] => usize::from(u16::from_be_bytes([hi, lo])),
+				[byte] => usize::from(byte),
+				_ => unreachable!(),
+			}));
+		} else {
+			for _ in 0..count {
+				samples.push(parser.number("sample")?);
+			}
+		}
+
+		if let Some(sample) = samples.iter().find(|&&sample| sample > maxval) {
+			return Err(invalid(format!(
+				"sample {} exceeds maxval {}",
+				sample, maxval
+			)));
+		}
+		let scale = |sample: usize| sample as f64 / maxval as f64;
+		let pixels = samples
+			.chunks(channels)
+			.map(|pixel| match *pixel {
+				[r, g, b] => Color::new(scale(r), scale(g), scale(b)),
+				[gray] => Color::new(scale(gray), scale(gray), scale(gray)),
+				_ => unreachable!(),
+			})
+			.collect();
+
+		Ok(Canvas {
+			width,
+			height,
+			pixels,
+		})
+	}
+
+	/// Stream the canvas as plain (P3) PPM with 8-bit samples
+	pub fn write_ppm<W: Write>(&self, w: W) -> io::Result<()> {
+		self.write_p3(w, BitDepth::Eight)
+	}
+
+	/// Stream the canvas as plain (P3) PPM one row at a time.
+	/// Rows are wrapped so that no line exceeds 70 characters.
+	pub fn write_p3<W: Write>(&self, mut w: W, depth: BitDepth) -> io::Result<()> {
+		let max = depth.max_value();
+		write!(w, "P3\n{} {}\n{}\n", self.width, self.height, max)?;
+
+		let mut line = String::with_capacity(MAX_LINE_LENGTH + 1);
+		for row in self.iter_rows() {
+			let samples = row
+				.flat_map(|pixel| [pixel.r, pixel.g, pixel.b])
+				.map(|value| clamp_and_normalize(value, max).to_string());
+			for sample in samples {
+				if !line.is_empty() && line.len() + 1 + sample.len() >= MAX_LINE_LENGTH {
+					line.push('\n');
+					w.write_all(line.as_bytes())?;
+					line.clear();
+				}
+				if !line.is_empty() {
+					line.push(' ');
+				}
+				line.push_str(&sample);
+			}
+			line.push('\n');
+			w.write_all(line.as_bytes())?;
+			line.clear();
+		}
+		Ok(())
+	}
+
+	/// Stream the canvas as raw (P6) PPM one row at a time
+	pub fn write_p6<W: Write>(&self, mut w: W, depth: BitDepth) -> io::Result<()> {
+		write!(
+			w,
+			"P6\n{} {}\n{}\n",
+			self.width,
+			self.height,
+			depth.max_value()
+		)?;
+
+		let mut buf = Vec::with_capacity(self.width * 3 * 2);
+		for row in self.iter_rows() {
+			for pixel in row {
+				depth.push_sample(pixel.r, &mut buf);
+				depth.push_sample(pixel.g, &mut buf);
+				depth.push_sample(pixel.b, &mut buf);
+			}
+			w.write_all(&buf)?;
+			buf.clear();
+		}
+		Ok(())
+	}
+}
+
+pub(super) fn invalid<S: Into<String>>(msg: S) -> Error {
+	Error::InvalidImage(msg.into())
+}
+
+/// Reads the whitespace separated numbers of a header or plain raster
+pub(super) struct Parser<'a> {
+	pub(super) data: &'a [u8],
+	pub(super) pos: usize,
+}
+
+impl Parser<'_> {
+	/// Skip whitespace and `#` comments, which run to the end of the line
+	pub(super) fn skip_whitespace(&mut self) {
+		while let Some(&byte) = self.data.get(self.pos) {
+			if byte == b'#' {
+				while self
+					.data
+					.get(self.pos)
+					.is_some_and(|&b| b != b'\n' && b != b'\r')
+				{
+					self.pos += 1;
+				}
+			} else if byte.is_ascii_whitespace() {
+				self.pos += 1;
+			} else {
+				break;
+			}
+		}
+	}
+
+	pub(super) fn number(&mut self, what: &str) -> Result<usize> {
+		self.skip_whitespace();
+		let start = self.pos;
+		while self.data.get(self.pos).is_some_and(u8::is_ascii_digit) {
+			self.pos += 1;
+		}
+		if start == self.pos {
+			return Err(match self.data.get(self.pos) {
+				Some(&byte) => invalid(format!(
+					"expected {} at byte {}, found {:?}",
+					what, start, byte as char
+				)),
+				None => invalid(format!("expected {}, found end of data", what)),
+			});
+		}
+		// Only ASCII digits were consumed, so this only fails on overflow
+		std::str::from_utf8(&self.data[start..self.pos])
+			.unwrap()
+			.parse()
+			.map_err(|_| invalid(format!("{} is too large", what)))
+	}
+
+	/// A decimal floating-point number such as `-1.0` or `1e-3`
+	pub(super) fn float(&mut self, what: &str) -> Result<f64> {
+		self.skip_whitespace();
+		let start = self.pos;
+		while self
+			.data
+			.get(self.pos)
+			.is_some_and(|b| !b.is_ascii_whitespace())
+		{
+			self.pos += 1;
+		}
+		std::str::from_utf8(&self.data[start..self.pos])
+			.ok()
+			.and_then(|token| token.parse().ok())
+			.ok_or_else(|| invalid(format!("expected {} at byte {}", what, start)))
+	}
+}
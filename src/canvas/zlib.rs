@@ -0,0 +1,197 @@
+//! Minimal zlib (RFC 1950) compressor using deflate (RFC 1951) with LZ77
+//! matching and the fixed Huffman codes, so no code tables have to be stored.
+
+const WINDOW_SIZE: usize = 1 << 15;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const HASH_BITS: u32 = 15;
+/// How many earlier positions with the same hash are tried before giving up
+const MAX_CHAIN: usize = 64;
+
+const LENGTH_BASE: [u16; 29] = [
+	3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+	163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+	0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+	1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+	2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+	0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+	13,
+];
+
+/// Compress `data` into a complete zlib stream
+pub fn compress(data: &[u8]) -> Vec<u8> {
+	// 32K window, deflate, no preset dictionary, fastest compression level
+	let mut out = BitWriter {
+		bytes: vec![0x78, 0x01],
+		bits: 0,
+		len: 0,
+	};
+
+	// A single final block using the fixed Huffman codes
+	out.write(1, 1);
+	out.write(1, 2);
+	deflate(data, &mut out);
+	write_literal(&mut out, 256);
+	out.flush();
+
+	let mut bytes = out.bytes;
+	bytes.extend_from_slice(&adler32(data).to_be_bytes());
+	bytes
+}
+
+/// The Adler-32 checksum zlib stores after the compressed data
+pub fn adler32(data: &[u8]) -> u32 {
+	const MOD: u32 = 65521;
+	let (mut a, mut b) = (1u32, 0u32);
+	// 5552 bytes is the most that can be summed before `b` could overflow
+	for chunk in data.chunks(5552) {
+		for &byte in chunk {
+			a += u32::from(byte);
+			b += a;
+		}
+		a %= MOD;
+		b %= MOD;
+	}
+	(b << 16) | a
+}
+
+/// Greedy LZ77: emit the longest earlier match at each position, or a literal
+fn deflate(data: &[u8], out: &mut BitWriter) {
+	// Most recent position per hash, and for each position the previous one with the same hash
+	let mut head = vec![usize::MAX; 1 << HASH_BITS];
+	let mut prev = vec![usize::MAX; WINDOW_SIZE];
+	let insert = |head: &mut [usize], prev: &mut [usize], i: usize| {
+		if i + MIN_MATCH <= data.len() {
+			let h = hash(&data[i..i + MIN_MATCH]);
+			prev[i % WINDOW_SIZE] = head[h];
+			head[h] = i;
+		}
+	};
+
+	let mut i = 0;
+	while i < data.len() {
+		let (length, distance) = longest_match(data, i, &head, &prev);
+		if length >= MIN_MATCH {
+			write_match(out, length, distance);
+			for j in i..i + length {
+				insert(&mut head, &mut prev, j);
+			}
+			i += length;
+		} else {
+			write_literal(out, u16::from(data[i]));
+			insert(&mut head, &mut prev, i);
+			i += 1;
+		}
+	}
+}
+
+fn hash(bytes: &[u8]) -> usize {
+	let key = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+	(key.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+fn longest_match(data: &[u8], i: usize, head: &[usize], prev: &[usize]) -> (usize, usize) {
+	if i + MIN_MATCH > data.len() {
+		return (0, 0);
+	}
+	let max = MAX_MATCH.min(data.len() - i);
+	let (mut best_length, mut best_distance) = (0, 0);
+	let mut candidate = head[hash(&data[i..i + MIN_MATCH])];
+	for _ in 0..MAX_CHAIN {
+		if candidate == usize::MAX || i - candidate > WINDOW_SIZE {
+			break;
+		}
+		let length = data[candidate..]
+			.iter()
+			.zip(&data[i..i + max])
+			.take_while(|(a, b)| a == b)
+			.count();
+		if length > best_length {
+			best_length = length;
+			best_distance = i - candidate;
+			if length == max {
+				break;
+			}
+		}
+		let next = prev[candidate % WINDOW_SIZE];
+		// Entries older than the window have been overwritten by newer positions
+		if next == usize::MAX || next >= candidate {
+			break;
+		}
+		candidate = next;
+	}
+	(best_length, best_distance)
+}
+
+/// Literal/length symbol using the fixed Huffman code
+fn write_literal(out: &mut BitWriter, symbol: u16) {
+	let (code, len) = match symbol {
+		0..=143 => (0x30 + symbol, 8),
+		144..=255 => (0x190 + symbol - 144, 9),
+		256..=279 => (symbol - 256, 7),
+		_ => (0xc0 + symbol - 280, 8),
+	};
+	out.write_code(u32::from(code), len);
+}
+
+fn write_match(out: &mut BitWriter, length: usize, distance: usize) {
+	let l = LENGTH_BASE
+		.iter()
+		.rposition(|&base| usize::from(base) <= length)
+		.unwrap();
+	write_literal(out, 257 + l as u16);
+	out.write(
+		(length - usize::from(LENGTH_BASE[l])) as u32,
+		LENGTH_EXTRA[l],
+	);
+
+	let d = DIST_BASE
+		.iter()
+		.rposition(|&base| usize::from(base) <= distance)
+		.unwrap();
+	out.write_code(d as u32, 5);
+	out.write((distance - usize::from(DIST_BASE[d])) as u32, DIST_EXTRA[d]);
+}
+
+/// Packs bits starting from the least significant bit of each byte
+struct BitWriter {
+	bytes: Vec<u8>,
+	bits: u32,
+	len: u8,
+}
+
+impl BitWriter {
+	/// Write the low `len` bits of `value`, least significant first
+	fn write(&mut self, value: u32, len: u8) {
+		for k in 0..len {
+			self.bits |= ((value >> k) & 1) << self.len;
+			self.len += 1;
+			if self.len == 8 {
+				self.bytes.push(self.bits as u8);
+				self.bits = 0;
+				self.len = 0;
+			}
+		}
+	}
+
+	/// Huffman codes are stored most significant bit first
+	fn write_code(&mut self, code: u32, len: u8) {
+		let reversed = code.reverse_bits() >> (32 - u32::from(len));
+		self.write(reversed, len);
+	}
+
+	/// Pad the last partial byte with zeros
+	fn flush(&mut self) {
+		if self.len > 0 {
+			self.bytes.push(self.bits as u8);
+			self.bits = 0;
+			self.len = 0;
+		}
+	}
+}
@@ -1,4 +1,13 @@
-use std::ops;
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops, str::FromStr};
+
+use crate::{
+	error::Error,
+	simd::Lanes,
+	utils::{parse_call, ApproxEq, Tolerance},
+};
 
 pub const BLACK: Color = Color {
 	r: 0.,
@@ -21,67 +30,84 @@ pub const GREEN: Color = Color {
 	b: 0.,
 };
 
-#[derive(Debug, Copy, Clone)]
-pub struct Color {
-	pub r: f64,
-	pub g: f64,
-	pub b: f64,
+/// A linear RGB color, generic over the floating-point precision
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Color<T = f64> {
+	pub r: T,
+	pub g: T,
+	pub b: T,
 }
 
-impl Color {
-	pub fn new(r: f64, g: f64, b: f64) -> Color {
+impl<T: Float> Color<T> {
+	pub fn new(r: T, g: T, b: T) -> Self {
 		Color { r, g, b }
 	}
+
+	/// The channels padded to four lanes
+	fn to_lanes(self) -> [T; 4] {
+		[self.r, self.g, self.b, T::zero()]
+	}
+
+	fn from_lanes(lanes: [T; 4]) -> Self {
+		Color::new(lanes[0], lanes[1], lanes[2])
+	}
 }
 
-impl PartialEq for Color {
-	fn eq(&self, other: &Self) -> bool {
-		super::approx_eq(self.r, other.r)
-			&& super::approx_eq(self.g, other.g)
-			&& super::approx_eq(self.b, other.b)
+impl<T: ApproxEq> ApproxEq for Color<T> {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.r.approx_eq_with(other.r, tolerance)
+			&& self.g.approx_eq_with(other.g, tolerance)
+			&& self.b.approx_eq_with(other.b, tolerance)
 	}
 }
 
-impl ops::Add<Color> for Color {
-	type Output = Color;
-	fn add(self, rhs: Color) -> Color {
-		Color {
-			r: self.r + rhs.r,
-			g: self.g + rhs.g,
-			b: self.b + rhs.b,
-		}
+impl<T: Lanes> ops::Add<Color<T>> for Color<T> {
+	type Output = Color<T>;
+	fn add(self, rhs: Color<T>) -> Color<T> {
+		Color::from_lanes(T::add4(self.to_lanes(), rhs.to_lanes()))
 	}
 }
 
-impl ops::Sub<Color> for Color {
-	type Output = Color;
-	fn sub(self, rhs: Color) -> Color {
-		Color {
-			r: self.r - rhs.r,
-			g: self.g - rhs.g,
-			b: self.b - rhs.b,
-		}
+impl<T: Lanes> ops::Sub<Color<T>> for Color<T> {
+	type Output = Color<T>;
+	fn sub(self, rhs: Color<T>) -> Color<T> {
+		Color::from_lanes(T::sub4(self.to_lanes(), rhs.to_lanes()))
 	}
 }
 
-impl ops::Mul<f64> for Color {
-	type Output = Color;
-	fn mul(self, rhs: f64) -> Color {
-		Color {
-			r: self.r * rhs,
-			g: self.g * rhs,
-			b: self.b * rhs,
-		}
+impl<T: Lanes> ops::Mul<T> for Color<T> {
+	type Output = Color<T>;
+	fn mul(self, rhs: T) -> Color<T> {
+		Color::from_lanes(T::scale4(self.to_lanes(), rhs))
+	}
+}
+
+impl<T: Lanes> ops::Mul<Color<T>> for Color<T> {
+	type Output = Color<T>;
+	fn mul(self, rhs: Color<T>) -> Color<T> {
+		Color::from_lanes(T::mul4(self.to_lanes(), rhs.to_lanes()))
 	}
 }
 
-impl ops::Mul<Color> for Color {
-	type Output = Color;
-	fn mul(self, rhs: Color) -> Color {
-		Color {
-			r: self.r * rhs.r,
-			g: self.g * rhs.g,
-			b: self.b * rhs.b,
+/// Parses `color(r, g, b)`
+impl<T: Copy + FromStr> FromStr for Color<T> {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (name, args) = parse_call(s)?;
+		match (name, args.as_slice()) {
+			("color", &[r, g, b]) => Ok(Color { r, g, b }),
+			_ => Err(Error::Parse(format!(
+				"expected color(r, g, b), found `{}`",
+				s.trim()
+			))),
 		}
 	}
 }
+
+impl<T: fmt::Display> fmt::Display for Color<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "color({}, {}, {})", self.r, self.g, self.b)
+	}
+}
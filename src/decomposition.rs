@@ -0,0 +1,89 @@
+use crate::{matrix, matrix::Matrix4x4, quaternion::Quaternion, transformations::*, tuple::Tuple};
+
+/// The components of an affine transformation.
+/// Recomposing them as `translation * rotation * shearing * scaling` yields the original matrix.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Decomposition {
+	/// Translation as a vector
+	pub translation: Tuple,
+	pub rotation: Quaternion,
+	/// Scale factors along x, y and z as a vector.
+	/// A reflection shows up as a negative x scale.
+	pub scale: Tuple,
+	/// Shear factors xy, xz and yz as taken by `shearing`.
+	/// The other three factors are always zero.
+	pub shear: [f64; 3],
+}
+
+impl Decomposition {
+	pub fn to_matrix(&self) -> Matrix4x4<f64> {
+		let [xy, xz, yz] = self.shear;
+		translation(self.translation.x, self.translation.y, self.translation.z)
+			* self.rotation.to_matrix()
+			* shearing(xy, xz, 0., yz, 0., 0.)
+			* scaling(self.scale.x, self.scale.y, self.scale.z)
+	}
+}
+
+impl Matrix4x4<f64> {
+	/// Split an affine matrix into translation, rotation, shear and scale.
+	/// Returns `None` if the last row is not (0, 0, 0, 1) or the matrix is singular.
+	pub fn decompose(&self) -> Option<Decomposition> {
+		if !self.is_affine() {
+			return None;
+		}
+		let col = |j: usize| Tuple::vector(self[(0, j)], self[(1, j)], self[(2, j)]);
+		// A column is dependent on the previous ones when what is left of it after
+		// Gram-Schmidt is lost in rounding, relative to the longest column like in `lu`
+		let tolerance = f64::EPSILON * 3. * (0..3).map(|j| col(j).magnitude()).fold(0., f64::max);
+
+		// Gram-Schmidt on the columns of the linear part gives `rotation * upper triangular`
+		let c0 = col(0);
+		let mut sx = c0.magnitude();
+		if sx <= tolerance {
+			return None;
+		}
+		let mut q0 = c0 / sx;
+
+		let c1 = col(1);
+		let mut u01 = q0.dot(&c1);
+		let c1 = c1 - q0 * u01;
+		let sy = c1.magnitude();
+		if sy <= tolerance {
+			return None;
+		}
+		let q1 = c1 / sy;
+
+		let c2 = col(2);
+		let mut u02 = q0.dot(&c2);
+		let u12 = q1.dot(&c2);
+		let c2 = c2 - q0 * u02 - q1 * u12;
+		let sz = c2.magnitude();
+		if sz <= tolerance {
+			return None;
+		}
+		let q2 = c2 / sz;
+
+		// a rotation can't flip handedness, so move the reflection into the scale
+		if q0.cross(&q1).dot(&q2) < 0. {
+			q0 = -q0;
+			sx = -sx;
+			u01 = -u01;
+			u02 = -u02;
+		}
+
+		let rotation = matrix![ 4, 4 =>
+			q0.x, q1.x, q2.x, 0.;
+			q0.y, q1.y, q2.y, 0.;
+			q0.z, q1.z, q2.z, 0.;
+			0.,   0.,   0.,   1.;
+		];
+
+		Some(Decomposition {
+			translation: Tuple::vector(self[(0, 3)], self[(1, 3)], self[(2, 3)]),
+			rotation: Quaternion::from_matrix(&rotation),
+			scale: Tuple::vector(sx, sy, sz),
+			shear: [u01 / sy, u02 / sz, u12 / sz],
+		})
+	}
+}
@@ -0,0 +1,104 @@
+use std::{fmt, io, sync::Arc};
+
+/// Errors reported by fallible operations throughout the crate
+#[derive(Debug, Clone)]
+pub enum Error {
+	/// A `(x, y)` coordinate lies outside of a `width` x `height` grid
+	OutOfBounds {
+		x: usize,
+		y: usize,
+		width: usize,
+		height: usize,
+	},
+	/// The number of elements supplied doesn't match the number required
+	DimensionMismatch { expected: usize, actual: usize },
+	/// The matrix has a zero determinant
+	NonInvertible,
+	/// Text could not be parsed into the requested type
+	Parse(String),
+	/// Image data is malformed or truncated
+	InvalidImage(String),
+	/// No encoder exists for the given file extension
+	UnsupportedFormat(String),
+	/// Reading or writing failed. Shared, so that `Error` stays `Clone`
+	Io(Arc<io::Error>),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::OutOfBounds {
+				x,
+				y,
+				width,
+				height,
+			} => write!(
+				f,
+				"index ({}, {}) is out of bounds for a {}x{} grid",
+				x, y, width, height
+			),
+			Error::DimensionMismatch { expected, actual } => {
+				write!(f, "expected {} elements but got {}", expected, actual)
+			}
+			Error::NonInvertible => write!(f, "matrix is not invertible"),
+			Error::Parse(msg) => write!(f, "parse error: {}", msg),
+			Error::InvalidImage(msg) => write!(f, "invalid image: {}", msg),
+			Error::UnsupportedFormat(ext) => write!(f, "unsupported image format `{}`", ext),
+			Error::Io(e) => write!(f, "i/o error: {}", e),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Error::Io(e) => Some(e.as_ref()),
+			_ => None,
+		}
+	}
+}
+
+/// `io::Error` has no equality, so i/o errors are equal if their kind and message are
+impl PartialEq for Error {
+	fn eq(&self, other: &Self) -> bool {
+		use Error::*;
+
+		match (self, other) {
+			(
+				OutOfBounds {
+					x,
+					y,
+					width,
+					height,
+				},
+				OutOfBounds {
+					x: x2,
+					y: y2,
+					width: width2,
+					height: height2,
+				},
+			) => (x, y, width, height) == (x2, y2, width2, height2),
+			(
+				DimensionMismatch { expected, actual },
+				DimensionMismatch {
+					expected: expected2,
+					actual: actual2,
+				},
+			) => (expected, actual) == (expected2, actual2),
+			(NonInvertible, NonInvertible) => true,
+			(Parse(a), Parse(b)) => a == b,
+			(InvalidImage(a), InvalidImage(b)) => a == b,
+			(UnsupportedFormat(a), UnsupportedFormat(b)) => a == b,
+			(Io(a), Io(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+			_ => false,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(Arc::new(e))
+	}
+}
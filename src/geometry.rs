@@ -0,0 +1,290 @@
+use std::{convert::TryFrom, ops};
+
+use crate::{
+	matrix::Matrix4x4,
+	tuple::Tuple,
+	utils::{ApproxEq, Tolerance},
+};
+
+/// A position in space
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point3 {
+	pub x: f64,
+	pub y: f64,
+	pub z: f64,
+}
+
+/// A direction and magnitude in space
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Vector3 {
+	pub x: f64,
+	pub y: f64,
+	pub z: f64,
+}
+
+/// A surface normal.
+/// Unlike vectors, normals have to be transformed by the inverse transpose of a transformation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Normal3 {
+	pub x: f64,
+	pub y: f64,
+	pub z: f64,
+}
+
+impl Point3 {
+	pub fn new(x: f64, y: f64, z: f64) -> Self {
+		Self { x, y, z }
+	}
+
+	pub fn origin() -> Self {
+		Self::new(0., 0., 0.)
+	}
+}
+
+impl Vector3 {
+	pub fn new(x: f64, y: f64, z: f64) -> Self {
+		Self { x, y, z }
+	}
+
+	pub fn magnitude(&self) -> f64 {
+		self.dot(self).sqrt()
+	}
+
+	pub fn normalize(&self) -> Self {
+		*self / self.magnitude()
+	}
+
+	pub fn dot(&self, v: &Vector3) -> f64 {
+		self.x * v.x + self.y * v.y + self.z * v.z
+	}
+
+	pub fn cross(&self, v: &Vector3) -> Self {
+		Vector3::new(
+			self.y * v.z - self.z * v.y,
+			self.z * v.x - self.x * v.z,
+			self.x * v.y - self.y * v.x,
+		)
+	}
+
+	/// Reflect the vector around the given normal
+	pub fn reflect(&self, normal: &Normal3) -> Self {
+		let n = Vector3::from(*normal);
+		*self - n * 2. * self.dot(&n)
+	}
+}
+
+impl Normal3 {
+	pub fn new(x: f64, y: f64, z: f64) -> Self {
+		Self { x, y, z }
+	}
+
+	pub fn normalize(&self) -> Self {
+		Vector3::from(*self).normalize().into()
+	}
+
+	pub fn dot(&self, v: &Vector3) -> f64 {
+		Vector3::from(*self).dot(v)
+	}
+
+	/// Transform the normal by the inverse transpose of a transformation and renormalize it
+	pub fn transform(&self, inverse_transpose: &Matrix4x4<f64>) -> Self {
+		let m = inverse_transpose;
+		let row = |i| m[(i, 0)] * self.x + m[(i, 1)] * self.y + m[(i, 2)] * self.z;
+		Normal3::new(row(0), row(1), row(2)).normalize()
+	}
+}
+
+impl Matrix4x4<f64> {
+	/// Transform a normal by the inverse transpose of this matrix.
+	/// Returns `None` if the matrix is not invertible.
+	pub fn transform_normal(&self, n: Normal3) -> Option<Normal3> {
+		self.invert_fast().map(|inv| n.transform(&inv.transpose()))
+	}
+}
+
+impl ApproxEq for Point3 {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.x.approx_eq_with(other.x, tolerance)
+			&& self.y.approx_eq_with(other.y, tolerance)
+			&& self.z.approx_eq_with(other.z, tolerance)
+	}
+}
+
+impl ApproxEq for Vector3 {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.x.approx_eq_with(other.x, tolerance)
+			&& self.y.approx_eq_with(other.y, tolerance)
+			&& self.z.approx_eq_with(other.z, tolerance)
+	}
+}
+
+impl ApproxEq for Normal3 {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.x.approx_eq_with(other.x, tolerance)
+			&& self.y.approx_eq_with(other.y, tolerance)
+			&& self.z.approx_eq_with(other.z, tolerance)
+	}
+}
+
+// Point arithmetic
+
+impl ops::Sub<Point3> for Point3 {
+	type Output = Vector3;
+	fn sub(self, rhs: Point3) -> Vector3 {
+		Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+	}
+}
+
+impl ops::Add<Vector3> for Point3 {
+	type Output = Point3;
+	fn add(self, rhs: Vector3) -> Point3 {
+		Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+	}
+}
+
+impl ops::Sub<Vector3> for Point3 {
+	type Output = Point3;
+	fn sub(self, rhs: Vector3) -> Point3 {
+		Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+	}
+}
+
+// Vector arithmetic
+
+impl ops::Add<Vector3> for Vector3 {
+	type Output = Vector3;
+	fn add(self, rhs: Vector3) -> Vector3 {
+		Vector3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+	}
+}
+
+impl ops::Sub<Vector3> for Vector3 {
+	type Output = Vector3;
+	fn sub(self, rhs: Vector3) -> Vector3 {
+		Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+	}
+}
+
+impl ops::Neg for Vector3 {
+	type Output = Vector3;
+	fn neg(self) -> Vector3 {
+		Vector3::new(-self.x, -self.y, -self.z)
+	}
+}
+
+impl ops::Mul<f64> for Vector3 {
+	type Output = Vector3;
+	fn mul(self, rhs: f64) -> Vector3 {
+		Vector3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+	}
+}
+
+impl ops::Div<f64> for Vector3 {
+	type Output = Vector3;
+	fn div(self, rhs: f64) -> Vector3 {
+		Vector3::new(self.x / rhs, self.y / rhs, self.z / rhs)
+	}
+}
+
+impl ops::Neg for Normal3 {
+	type Output = Normal3;
+	fn neg(self) -> Normal3 {
+		Normal3::new(-self.x, -self.y, -self.z)
+	}
+}
+
+// Transformations
+
+impl ops::Mul<Point3> for &Matrix4x4<f64> {
+	type Output = Point3;
+	fn mul(self, rhs: Point3) -> Point3 {
+		let t = self * Tuple::from(rhs);
+		Point3::new(t.x / t.w, t.y / t.w, t.z / t.w)
+	}
+}
+
+impl ops::Mul<Point3> for Matrix4x4<f64> {
+	type Output = Point3;
+	fn mul(self, rhs: Point3) -> Point3 {
+		ops::Mul::mul(&self, rhs)
+	}
+}
+
+impl ops::Mul<Vector3> for &Matrix4x4<f64> {
+	type Output = Vector3;
+	fn mul(self, rhs: Vector3) -> Vector3 {
+		let t = self * Tuple::from(rhs);
+		Vector3::new(t.x, t.y, t.z)
+	}
+}
+
+impl ops::Mul<Vector3> for Matrix4x4<f64> {
+	type Output = Vector3;
+	fn mul(self, rhs: Vector3) -> Vector3 {
+		ops::Mul::mul(&self, rhs)
+	}
+}
+
+// Conversions
+
+impl From<Normal3> for Vector3 {
+	fn from(n: Normal3) -> Self {
+		Vector3::new(n.x, n.y, n.z)
+	}
+}
+
+impl From<Vector3> for Normal3 {
+	fn from(v: Vector3) -> Self {
+		Normal3::new(v.x, v.y, v.z)
+	}
+}
+
+impl From<Point3> for Tuple {
+	fn from(p: Point3) -> Self {
+		Tuple::point(p.x, p.y, p.z)
+	}
+}
+
+impl From<Vector3> for Tuple {
+	fn from(v: Vector3) -> Self {
+		Tuple::vector(v.x, v.y, v.z)
+	}
+}
+
+impl From<Normal3> for Tuple {
+	fn from(n: Normal3) -> Self {
+		Tuple::vector(n.x, n.y, n.z)
+	}
+}
+
+/// Fails with the original tuple if it is not a point
+impl TryFrom<Tuple> for Point3 {
+	type Error = Tuple;
+	fn try_from(t: Tuple) -> Result<Self, Self::Error> {
+		if t.is_point() {
+			Ok(Point3::new(t.x, t.y, t.z))
+		} else {
+			Err(t)
+		}
+	}
+}
+
+/// Fails with the original tuple if it is not a vector
+impl TryFrom<Tuple> for Vector3 {
+	type Error = Tuple;
+	fn try_from(t: Tuple) -> Result<Self, Self::Error> {
+		if t.is_vector() {
+			Ok(Vector3::new(t.x, t.y, t.z))
+		} else {
+			Err(t)
+		}
+	}
+}
+
+/// Fails with the original tuple if it is not a vector
+impl TryFrom<Tuple> for Normal3 {
+	type Error = Tuple;
+	fn try_from(t: Tuple) -> Result<Self, Self::Error> {
+		Vector3::try_from(t).map(Normal3::from)
+	}
+}
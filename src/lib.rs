@@ -1,11 +1,19 @@
 pub mod canvas;
 pub mod color;
+pub mod decomposition;
+pub mod error;
+pub mod geometry;
 pub mod matrix;
+pub mod quaternion;
+pub mod ray;
+pub mod simd;
+pub mod transform;
+pub mod transformations;
 pub mod tuple;
 pub mod utils;
 
-const EPSILON: f64 = 0.00001;
-
-pub fn approx_eq(a: f64, b: f64) -> bool {
-	(a - b).abs() < EPSILON
+/// Whether `a` and `b` differ by less than the default tolerance `utils::EPSILON`
+#[deprecated(note = "use `utils::ApproxEq::approx_eq`")]
+pub fn approx_eq<T: utils::ApproxEq>(a: T, b: T) -> bool {
+	a.approx_eq(b)
 }
@@ -1,29 +1,39 @@
-use super::{Dim, Matrix, M1, M4};
-use crate::tuple::Tuple;
-use std::marker::PhantomData;
+use std::convert::TryFrom;
 
-impl<T: Into<f64> + Copy> Matrix<T, M4, M1> {
-	pub fn to_tuple(self) -> Tuple {
-		Tuple::new(
-			self.data[0].into(),
-			self.data[1].into(),
-			self.data[2].into(),
-			self.data[3].into(),
-		)
+use num_traits::Num;
+
+use super::Matrix;
+use crate::{error::Error, tuple::Tuple};
+
+impl<T: Num + Copy> Matrix<T, 4, 1> {
+	pub fn to_tuple(self) -> Tuple<T> {
+		Tuple {
+			x: self[(0, 0)],
+			y: self[(1, 0)],
+			z: self[(2, 0)],
+			w: self[(3, 0)],
+		}
 	}
 }
 
-impl<T, M, N> From<Vec<T>> for Matrix<T, M, N>
-where
-	M: Dim,
-	N: Dim,
-{
-	fn from(v: Vec<T>) -> Self {
-		assert_eq!(v.len(), M::SIZE * N::SIZE);
-		Matrix {
-			data: v,
-			_m: PhantomData,
-			_n: PhantomData,
+impl<T, const M: usize, const N: usize> From<[[T; N]; M]> for Matrix<T, M, N> {
+	fn from(data: [[T; N]; M]) -> Self {
+		Matrix { data }
+	}
+}
+
+/// Fills the matrix in row-major order, failing unless exactly `M * N` values are given
+impl<T, const M: usize, const N: usize> TryFrom<Vec<T>> for Matrix<T, M, N> {
+	type Error = Error;
+
+	fn try_from(v: Vec<T>) -> Result<Self, Self::Error> {
+		if v.len() != M * N {
+			return Err(Error::DimensionMismatch {
+				expected: M * N,
+				actual: v.len(),
+			});
 		}
+		let mut values = v.into_iter();
+		Ok(Matrix::from_fn(|_, _| values.next().unwrap()))
 	}
 }
@@ -1,6 +1,6 @@
-use super::{Dim, Matrix, Sub, M2, M3, M4};
-use num_traits::{Num, Signed};
-use std::{iter::Sum, ops::DivAssign};
+use super::Matrix;
+use crate::error::{Error, Result};
+use num_traits::Num;
 
 pub trait Determinant<T> {
 	fn minor(&self, i: usize, j: usize) -> T;
@@ -8,150 +8,154 @@ pub trait Determinant<T> {
 	fn det(&self) -> T;
 }
 
-impl<T: Num + Copy> Determinant<T> for Matrix<T, M2, M2> {
+impl<T: MatrixElement, const N: usize> Determinant<T> for Matrix<T, N, N> {
 	fn det(&self) -> T {
-		match self.data[..] {
-			[a, b, c, d] => a * d - b * c,
-			_ => unreachable!(),
-		}
-	}
-
-	fn minor(&self, _: usize, _: usize) -> T {
-		unimplemented!()
-	}
-
-	fn cofactor(&self, _: usize, _: usize) -> T {
-		unimplemented!()
-	}
-}
-
-impl<T: Num + Copy + Signed + Sum> Determinant<T> for Matrix<T, M3, M3> {
-	fn det(&self) -> T {
-		(0..3).map(|j| self[(0, j)] * self.cofactor(0, j)).sum()
+		T::matrix_det(self)
 	}
 
+	/// Determinant of the submatrix without row i and column j.
+	/// `submatrix` needs the size `N - 1` spelled out, so the submatrix is kept
+	/// in the upper-left corner of an `N`x`N` matrix instead, bordered by
+	/// a 1 on the diagonal, which leaves the determinant unchanged.
 	fn minor(&self, i: usize, j: usize) -> T {
-		self.submatrix(i, j).det()
+		Matrix::<T, N, N>::from_fn(|x, y| match (x + 1 == N, y + 1 == N) {
+			(true, true) => T::one(),
+			(false, false) => self[(if x < i { x } else { x + 1 }, if y < j { y } else { y + 1 })],
+			_ => T::zero(),
+		})
+		.det()
 	}
 
 	fn cofactor(&self, i: usize, j: usize) -> T {
 		if (i + j) % 2 == 0 {
 			self.minor(i, j)
 		} else {
-			-T::one() * self.minor(i, j)
+			T::zero() - self.minor(i, j)
 		}
 	}
 }
 
-impl<T: Num + Copy + Signed + Sum> Determinant<T> for Matrix<T, M4, M4> {
-	fn minor(&self, i: usize, j: usize) -> T {
-		self.submatrix(i, j).det()
+impl<T, const M: usize, const N: usize> Matrix<T, M, N>
+where
+	T: Copy,
+{
+	/// Remove the i'th row and j'th column.
+	/// `P` and `Q` must be `M - 1` and `N - 1`, which is checked at compile time.
+	pub fn submatrix<const P: usize, const Q: usize>(&self, i: usize, j: usize) -> Matrix<T, P, Q> {
+		self.remove_row::<P>(i).remove_col::<Q>(j)
 	}
 
-	fn cofactor(&self, i: usize, j: usize) -> T {
-		if (i + j) % 2 == 0 {
-			self.minor(i, j)
-		} else {
-			-T::one() * self.minor(i, j)
-		}
+	/// Remove the i'th row. `P` must be `M - 1`
+	pub fn remove_row<const P: usize>(&self, i: usize) -> Matrix<T, P, N> {
+		const {
+			assert!(
+				P + 1 == M,
+				"remove_row must yield a matrix with one row less"
+			)
+		};
+		Matrix::from_fn(|x, y| self[(if x < i { x } else { x + 1 }, y)])
 	}
 
-	fn det(&self) -> T {
-		(0..4).map(|j| self[(0, j)] * self.cofactor(0, j)).sum()
+	/// Remove the j'th col. `Q` must be `N - 1`
+	pub fn remove_col<const Q: usize>(&self, j: usize) -> Matrix<T, M, Q> {
+		const {
+			assert!(
+				Q + 1 == N,
+				"remove_col must yield a matrix with one column less"
+			)
+		};
+		Matrix::from_fn(|x, y| self[(x, if y < j { y } else { y + 1 })])
 	}
 }
 
-impl<T, M, N> Matrix<T, M, N>
-where
-	T: Copy,
-	M: Dim + Sub,
-	N: Dim + Sub,
-	<M as Sub>::SUB: Dim,
-	<N as Sub>::SUB: Dim,
-{
-	pub fn submatrix(&self, i: usize, j: usize) -> Matrix<T, M::SUB, N::SUB> {
-		self.remove_row(i).remove_col(j)
+/// Element types that know how to take the determinant and inverse of a square matrix of themselves.
+/// The defaults are exact for integers. Floats use the LU decomposition instead,
+/// which pivots on the largest element and rejects matrices that are singular to working precision.
+/// Other numeric types opt in with an empty `impl MatrixElement for MyNumber {}`.
+pub trait MatrixElement: Num + Copy {
+	fn matrix_det<const N: usize>(m: &Matrix<Self, N, N>) -> Self {
+		bareiss(m)
+	}
+
+	fn invert_matrix<const N: usize>(m: &Matrix<Self, N, N>) -> Option<Matrix<Self, N, N>> {
+		invert_by_cofactors(m)
 	}
 }
 
-impl<T, M, N> Matrix<T, M, N>
-where
-	T: Copy,
-	M: Dim + Sub,
-	N: Dim,
-	<M as Sub>::SUB: Dim,
-{
-	/// Remove the i'th row
-	pub fn remove_row(&self, i: usize) -> Matrix<T, M::SUB, N> {
-		let mut m = Matrix::new_uninitialized();
-		m.data = self
-			.iter_rows()
-			.enumerate()
-			.filter_map(|(x, row)| if x != i { Some(row) } else { None })
-			.flatten()
-			.copied()
-			.collect::<Vec<T>>();
-		m
+macro_rules! by_lu {
+	($($t:ty),*) => {$(
+		impl MatrixElement for $t {
+			fn matrix_det<const N: usize>(m: &Matrix<Self, N, N>) -> Self {
+				m.lu().map_or(0., |lu| lu.det())
+			}
+
+			fn invert_matrix<const N: usize>(m: &Matrix<Self, N, N>) -> Option<Matrix<Self, N, N>> {
+				m.lu().map(|lu| lu.invert())
+			}
+		}
+	)*};
+}
+
+macro_rules! exact {
+	($($t:ty),*) => {$(
+		impl MatrixElement for $t {}
+	)*};
+}
+
+by_lu!(f32, f64);
+exact!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Fraction-free Gaussian elimination (Bareiss algorithm).
+/// Every division is exact, so this works for integer matrices.
+fn bareiss<T: Num + Copy, const N: usize>(m: &Matrix<T, N, N>) -> T {
+	if N == 0 {
+		return T::one();
+	}
+	let mut a = m.data;
+	let mut sign = T::one();
+	let mut prev = T::one();
+	for k in 0..N {
+		if a[k][k] == T::zero() {
+			match (k + 1..N).find(|&r| a[r][k] != T::zero()) {
+				Some(r) => {
+					a.swap(k, r);
+					sign = T::zero() - sign;
+				}
+				None => return T::zero(),
+			}
+		}
+		for i in k + 1..N {
+			for j in k + 1..N {
+				a[i][j] = (a[i][j] * a[k][k] - a[i][k] * a[k][j]) / prev;
+			}
+		}
+		prev = a[k][k];
 	}
+	sign * a[N - 1][N - 1]
 }
 
-impl<T, M, N> Matrix<T, M, N>
-where
-	T: Copy,
-	M: Dim,
-	N: Dim + Sub,
-	<N as Sub>::SUB: Dim,
-{
-	/// Remove the j'th col
-	pub fn remove_col(&self, j: usize) -> Matrix<T, M, N::SUB> {
-		let mut m = Matrix::new_uninitialized();
-		m.data = self
-			.iter_cols()
-			.enumerate()
-			.filter_map(|(x, col)| if x != j { Some(col) } else { None })
-			.fold(None, |old: Option<Vec<Vec<_>>>, new| match old {
-				// zip all the iterators
-				// TODO Maybe test another datastructure here - VecDeque or LinkedList or smth
-				// Could also transpose -> remove row -> transpose, if that is more efficient
-				Some(it) => Some(
-					it.into_iter()
-						.zip(new)
-						.map(|(mut v, elem)| {
-							v.push(elem);
-							v
-						})
-						.collect::<Vec<Vec<_>>>(),
-				),
-				None => Some(new.map(|x| vec![x]).collect::<Vec<_>>()),
-			})
-			.unwrap()
-			.into_iter()
-			.flatten()
-			.copied()
-			.collect::<Vec<T>>();
-		m
+/// Adjugate divided by the determinant, exact for integer elements
+fn invert_by_cofactors<T: MatrixElement, const N: usize>(
+	m: &Matrix<T, N, N>,
+) -> Option<Matrix<T, N, N>> {
+	let det = m.det();
+	if det == T::zero() {
+		None
+	} else {
+		Some(Matrix::from_fn(|i, j| m.cofactor(j, i) / det))
 	}
 }
 
-impl<T, M> Matrix<T, M, M>
+impl<T, const M: usize> Matrix<T, M, M>
 where
-	T: Num + Copy + DivAssign,
-	M: Dim,
-	Self: Determinant<T>,
+	T: MatrixElement,
 {
 	pub fn invert(&self) -> Option<Self> {
-		let det = self.det();
-		if det == T::zero() {
-			None
-		} else {
-			let mut m = Self::new_uninitialized();
-			m.data = self
-				.iter_indexed()
-				.map(|(i, j, _)| self.cofactor(i, j))
-				.collect::<Vec<_>>();
-			let mut m2 = m.transpose();
-			let _ = m2.iter_mut().map(|x| *x /= det).collect::<Vec<_>>();
-			Some(m2)
-		}
+		T::invert_matrix(self)
+	}
+
+	/// Like `invert`, but reports a singular matrix as `Error::NonInvertible`
+	pub fn try_invert(&self) -> Result<Self> {
+		self.invert().ok_or(Error::NonInvertible)
 	}
 }
@@ -0,0 +1,116 @@
+use super::Matrix4x4;
+
+impl Matrix4x4<f64> {
+	/// Whether the last row is (0, 0, 0, 1), i.e. the matrix only combines a linear map and a translation
+	pub fn is_affine(&self) -> bool {
+		self.data[3] == [0., 0., 0., 1.]
+	}
+
+	/// Closed-form inverse that does not go through cofactor submatrices.
+	/// Uses the cheaper affine inverse if the last row is (0, 0, 0, 1).
+	pub fn invert_fast(&self) -> Option<Self> {
+		if self.is_affine() {
+			return self.invert_affine();
+		}
+
+		let a = &self.data;
+		let s0 = a[0][0] * a[1][1] - a[1][0] * a[0][1];
+		let s1 = a[0][0] * a[1][2] - a[1][0] * a[0][2];
+		let s2 = a[0][0] * a[1][3] - a[1][0] * a[0][3];
+		let s3 = a[0][1] * a[1][2] - a[1][1] * a[0][2];
+		let s4 = a[0][1] * a[1][3] - a[1][1] * a[0][3];
+		let s5 = a[0][2] * a[1][3] - a[1][2] * a[0][3];
+
+		let c5 = a[2][2] * a[3][3] - a[3][2] * a[2][3];
+		let c4 = a[2][1] * a[3][3] - a[3][1] * a[2][3];
+		let c3 = a[2][1] * a[3][2] - a[3][1] * a[2][2];
+		let c2 = a[2][0] * a[3][3] - a[3][0] * a[2][3];
+		let c1 = a[2][0] * a[3][2] - a[3][0] * a[2][2];
+		let c0 = a[2][0] * a[3][1] - a[3][0] * a[2][1];
+
+		let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+		if is_singular(det, self, 4) {
+			return None;
+		}
+		let inv = 1. / det;
+
+		Some(Matrix4x4::from([
+			[
+				(a[1][1] * c5 - a[1][2] * c4 + a[1][3] * c3) * inv,
+				(-a[0][1] * c5 + a[0][2] * c4 - a[0][3] * c3) * inv,
+				(a[3][1] * s5 - a[3][2] * s4 + a[3][3] * s3) * inv,
+				(-a[2][1] * s5 + a[2][2] * s4 - a[2][3] * s3) * inv,
+			],
+			[
+				(-a[1][0] * c5 + a[1][2] * c2 - a[1][3] * c1) * inv,
+				(a[0][0] * c5 - a[0][2] * c2 + a[0][3] * c1) * inv,
+				(-a[3][0] * s5 + a[3][2] * s2 - a[3][3] * s1) * inv,
+				(a[2][0] * s5 - a[2][2] * s2 + a[2][3] * s1) * inv,
+			],
+			[
+				(a[1][0] * c4 - a[1][1] * c2 + a[1][3] * c0) * inv,
+				(-a[0][0] * c4 + a[0][1] * c2 - a[0][3] * c0) * inv,
+				(a[3][0] * s4 - a[3][1] * s2 + a[3][3] * s0) * inv,
+				(-a[2][0] * s4 + a[2][1] * s2 - a[2][3] * s0) * inv,
+			],
+			[
+				(-a[1][0] * c3 + a[1][1] * c1 - a[1][2] * c0) * inv,
+				(a[0][0] * c3 - a[0][1] * c1 + a[0][2] * c0) * inv,
+				(-a[3][0] * s3 + a[3][1] * s1 - a[3][2] * s0) * inv,
+				(a[2][0] * s3 - a[2][1] * s1 + a[2][2] * s0) * inv,
+			],
+		]))
+	}
+
+	/// Inverse of an affine matrix `[A t; 0 1]`, which is `[A⁻¹ -A⁻¹t; 0 1]`.
+	/// The last row is assumed to be (0, 0, 0, 1) and is not read.
+	pub fn invert_affine(&self) -> Option<Self> {
+		let a = &self.data;
+		// cofactors of the upper-left 3x3 block
+		let c00 = a[1][1] * a[2][2] - a[1][2] * a[2][1];
+		let c01 = a[1][2] * a[2][0] - a[1][0] * a[2][2];
+		let c02 = a[1][0] * a[2][1] - a[1][1] * a[2][0];
+
+		let det = a[0][0] * c00 + a[0][1] * c01 + a[0][2] * c02;
+		if is_singular(det, self, 3) {
+			return None;
+		}
+		let inv = 1. / det;
+
+		let r = [
+			[
+				c00 * inv,
+				(a[0][2] * a[2][1] - a[0][1] * a[2][2]) * inv,
+				(a[0][1] * a[1][2] - a[0][2] * a[1][1]) * inv,
+			],
+			[
+				c01 * inv,
+				(a[0][0] * a[2][2] - a[0][2] * a[2][0]) * inv,
+				(a[0][2] * a[1][0] - a[0][0] * a[1][2]) * inv,
+			],
+			[
+				c02 * inv,
+				(a[0][1] * a[2][0] - a[0][0] * a[2][1]) * inv,
+				(a[0][0] * a[1][1] - a[0][1] * a[1][0]) * inv,
+			],
+		];
+		let t = |i: usize| -(r[i][0] * a[0][3] + r[i][1] * a[1][3] + r[i][2] * a[2][3]);
+
+		Some(Matrix4x4::from([
+			[r[0][0], r[0][1], r[0][2], t(0)],
+			[r[1][0], r[1][1], r[1][2], t(1)],
+			[r[2][0], r[2][1], r[2][2], t(2)],
+			[0., 0., 0., 1.],
+		]))
+	}
+}
+
+/// Whether `det` of the upper-left `n`x`n` block of `m` is zero to working precision.
+/// The tolerance scales with the block's 1-norm raised to `n`, like the determinant
+/// itself, so uniformly small or large matrices are judged the same way.
+fn is_singular(det: f64, m: &Matrix4x4<f64>, n: usize) -> bool {
+	let norm = (0..n)
+		.map(|j| (0..n).map(|i| m.data[i][j].abs()).sum::<f64>())
+		.fold(0., f64::max);
+	det.is_nan() || det.abs() <= f64::EPSILON * n as f64 * norm.powi(n as i32)
+}
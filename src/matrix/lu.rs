@@ -0,0 +1,110 @@
+use super::Matrix;
+use num_traits::Float;
+
+/// LU decomposition with partial pivoting, such that `P * A = L * U`
+#[derive(Debug, Copy, Clone)]
+pub struct Lu<T, const N: usize> {
+	/// L below the diagonal (with an implied unit diagonal) and U on and above it
+	lu: Matrix<T, N, N>,
+	/// Row i of `P * A` is row `perm[i]` of `A`
+	perm: [usize; N],
+	/// Determinant of the permutation, either 1 or -1
+	sign: T,
+	/// 1-norm of `A`, kept for the condition number
+	norm: T,
+}
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+	/// Decompose the matrix into a lower and an upper triangular matrix.
+	/// Returns `None` if the matrix is singular to working precision.
+	pub fn lu(&self) -> Option<Lu<T, N>> {
+		let norm = norm_1(self);
+		let tolerance = T::epsilon() * T::from(N).unwrap() * norm;
+		let mut lu = *self;
+		let mut perm: [usize; N] = std::array::from_fn(|i| i);
+		let mut sign = T::one();
+
+		for k in 0..N {
+			// NaN is never a pivot, and a NaN norm makes every pivot fail the tolerance check
+			let pivot = match (k..N)
+				.filter(|&i| !lu[(i, k)].is_nan())
+				.max_by(|&a, &b| lu[(a, k)].abs().partial_cmp(&lu[(b, k)].abs()).unwrap())
+			{
+				Some(pivot) if lu[(pivot, k)].abs() > tolerance => pivot,
+				_ => return None,
+			};
+			if pivot != k {
+				lu.data.swap(pivot, k);
+				perm.swap(pivot, k);
+				sign = -sign;
+			}
+			for i in k + 1..N {
+				let factor = lu[(i, k)] / lu[(k, k)];
+				lu[(i, k)] = factor;
+				for j in k + 1..N {
+					lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+				}
+			}
+		}
+
+		Some(Lu {
+			lu,
+			perm,
+			sign,
+			norm,
+		})
+	}
+
+	/// Solve `self * x = b` for x.
+	/// Returns `None` if the matrix is singular.
+	pub fn solve<const K: usize>(&self, b: &Matrix<T, N, K>) -> Option<Matrix<T, N, K>> {
+		self.lu().map(|lu| lu.solve(b))
+	}
+}
+
+impl<T: Float, const N: usize> Lu<T, N> {
+	pub fn det(&self) -> T {
+		(0..N).fold(self.sign, |det, i| det * self.lu[(i, i)])
+	}
+
+	/// Solve `A * x = b` for x, where each column of `b` is a separate right-hand side
+	pub fn solve<const K: usize>(&self, b: &Matrix<T, N, K>) -> Matrix<T, N, K> {
+		let mut x = Matrix::from_fn(|i, j| b[(self.perm[i], j)]);
+		for col in 0..K {
+			// forward substitution with L
+			for i in 0..N {
+				let mut sum = x[(i, col)];
+				for k in 0..i {
+					sum = sum - self.lu[(i, k)] * x[(k, col)];
+				}
+				x[(i, col)] = sum;
+			}
+			// back substitution with U
+			for i in (0..N).rev() {
+				let mut sum = x[(i, col)];
+				for k in i + 1..N {
+					sum = sum - self.lu[(i, k)] * x[(k, col)];
+				}
+				x[(i, col)] = sum / self.lu[(i, i)];
+			}
+		}
+		x
+	}
+
+	pub fn invert(&self) -> Matrix<T, N, N> {
+		self.solve(&Matrix::identity())
+	}
+
+	/// Condition number of `A` in the 1-norm.
+	/// Large values mean results computed with this matrix lose precision.
+	pub fn cond(&self) -> T {
+		self.norm * norm_1(&self.invert())
+	}
+}
+
+/// Maximum absolute column sum
+fn norm_1<T: Float, const N: usize>(m: &Matrix<T, N, N>) -> T {
+	m.iter_cols()
+		.map(|col| col.fold(T::zero(), |sum, x| sum + x.abs()))
+		.fold(T::zero(), T::max)
+}
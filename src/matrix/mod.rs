@@ -1,132 +1,122 @@
-use crate::utils::ApproxEq;
+use crate::utils::{ApproxEq, Tolerance};
 use fmt::Debug;
 use num_traits::{One, Zero};
-use std::{fmt, marker::PhantomData};
+use std::fmt;
 
 mod convert;
 mod determinant;
+mod inverse;
 mod iter;
+mod lu;
 mod macros;
 mod ops;
-mod typestate;
+mod parse;
+#[cfg(feature = "serde")]
+mod serialize;
 
 pub use determinant::*;
-pub use macros::*;
-pub use typestate::*;
-
-pub type Matrix2x2<T> = Matrix<T, M2, M2>;
-pub type Matrix3x3<T> = Matrix<T, M3, M3>;
-pub type Matrix4x4<T> = Matrix<T, M4, M4>;
-
-#[derive(Clone, PartialEq)]
-pub struct Matrix<T, M: Dim, N: Dim> {
-	_m: PhantomData<M>,
-	_n: PhantomData<N>,
-	pub data: Vec<T>,
+pub use lu::*;
+
+pub type Matrix2x2<T> = Matrix<T, 2, 2>;
+pub type Matrix3x3<T> = Matrix<T, 3, 3>;
+pub type Matrix4x4<T> = Matrix<T, 4, 4>;
+
+/// A matrix with `M` rows and `N` columns, stored in row-major order
+#[derive(Copy, Clone, PartialEq)]
+pub struct Matrix<T, const M: usize, const N: usize> {
+	pub data: [[T; N]; M],
 }
 
-impl<T, M, N> Matrix<T, M, N>
+impl<T, const M: usize, const N: usize> Matrix<T, M, N>
 where
-	T: Default + Clone,
-	M: Dim,
-	N: Dim,
+	T: Default,
 {
 	pub fn new() -> Self {
-		let data = vec![T::default(); M::SIZE * N::SIZE];
-		Self {
-			_m: PhantomData,
-			_n: PhantomData,
-			data,
-		}
+		Self::from_fn(|_, _| T::default())
+	}
+
+	/// Kept for existing callers, who always overwrote every element anyway.
+	/// A fixed-size array cannot be left uninitialized, so this is `new`.
+	#[deprecated(note = "matrices are always initialized now, use `new` or `from_fn`")]
+	pub fn new_uninitialized() -> Self {
+		Self::new()
 	}
 }
 
-impl<T, M, N> Matrix<T, M, N>
+impl<T, const M: usize, const N: usize> Default for Matrix<T, M, N>
 where
-	M: Dim,
-	N: Dim,
+	T: Default,
 {
-	pub fn new_uninitialized() -> Self {
-		let data = Vec::with_capacity(M::SIZE * N::SIZE);
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N> {
+	/// Create a matrix where each element is produced by calling `f` with its (i, j) coordinates.
+	/// Elements are produced in row-major order.
+	pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Self {
 		Self {
-			_m: PhantomData,
-			_n: PhantomData,
-			data,
+			data: std::array::from_fn(|i| std::array::from_fn(|j| f(i, j))),
 		}
 	}
 
 	pub fn width(&self) -> usize {
-		M::SIZE
+		M
 	}
 
 	pub fn height(&self) -> usize {
-		N::SIZE
+		N
 	}
 
 	pub fn to_row_major(i: usize, j: usize) -> usize {
-		N::SIZE * i + j
+		N * i + j
 	}
 }
 
-impl<T, M, N> Matrix<T, M, N>
+impl<T, const M: usize, const N: usize> Matrix<T, M, N>
 where
 	T: Copy,
-	M: Dim,
-	N: Dim,
 {
 	pub fn transpose(&self) -> Matrix<T, N, M> {
-		let mut v = Vec::with_capacity(M::SIZE * N::SIZE);
-		for i in 0..M::SIZE {
-			for j in 0..N::SIZE {
-				v.push(self[(j, i)]);
-			}
-		}
-		Matrix::from(v)
+		Matrix::from_fn(|i, j| self[(j, i)])
 	}
 }
 
-impl<T, M> Matrix<T, M, M>
+impl<T, const M: usize> Matrix<T, M, M>
 where
 	T: One + Zero + Copy,
-	M: Dim,
 {
 	pub fn identity() -> Self {
-		let mut m = Matrix::new_uninitialized();
-		m.data = vec![T::zero(); M::SIZE * M::SIZE];
-		for i in 0..M::SIZE {
-			m[(i, i)] = T::one();
-		}
-		m
+		Matrix::from_fn(|i, j| if i == j { T::one() } else { T::zero() })
 	}
 }
 
-impl<T, M, N> Debug for Matrix<T, M, N>
+impl<T, const M: usize, const N: usize> Debug for Matrix<T, M, N>
 where
 	T: Debug,
-	M: Dim,
-	N: Dim,
 {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		writeln!(f, "Matrix {}x{}", M::SIZE, N::SIZE)?;
+		writeln!(f, "Matrix {}x{}", M, N)?;
 
-		for x in 0..M::SIZE {
-			for y in 0..N::SIZE {
-				write!(f, "{:?} ", self.data[x * N::SIZE + y])?;
+		for x in 0..M {
+			for y in 0..N {
+				write!(f, "{:?} ", self[(x, y)])?;
 			}
-			writeln!(f, "")?;
+			writeln!(f)?;
 		}
 
 		Ok(())
 	}
 }
 
-impl<T, M, N> ApproxEq for &Matrix<T, M, N>
+impl<T, const M: usize, const N: usize> ApproxEq for &Matrix<T, M, N>
 where
 	T: ApproxEq + Copy,
-	M: Dim,
-	N: Dim,
 {
-	fn approx_eq(self, other: Self) -> bool {
-		self.iter().zip(other.iter()).all(|(l, r)| l.approx_eq(*r))
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.iter()
+			.zip(other.iter())
+			.all(|(l, r)| l.approx_eq_with(*r, tolerance))
 	}
 }
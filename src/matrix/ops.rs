@@ -1,118 +1,228 @@
-use super::{Dim, Matrix};
-use std::{iter::Sum, ops::{Index, IndexMut, Mul}};
+use super::{Matrix, Matrix4x4};
+use crate::{simd::Lanes, tuple::Tuple};
+use num_traits::Zero;
+use std::{
+	iter::Sum,
+	ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
 
 // Index
 
-impl<T, M, N> Index<(usize, usize)> for Matrix<T, M, N>
-where
-	M: Dim,
-	N: Dim,
-{
+impl<T, const M: usize, const N: usize> Index<(usize, usize)> for Matrix<T, M, N> {
 	type Output = T;
 
 	fn index(&self, coords: (usize, usize)) -> &Self::Output {
 		let (i, j) = coords;
-		&self.data[Self::to_row_major(i, j)]
+		&self.data[i][j]
 	}
 }
 
-impl<T, M, N> IndexMut<(usize, usize)> for Matrix<T, M, N>
-where
-	M: Dim,
-	N: Dim,
-{
+impl<T, const M: usize, const N: usize> IndexMut<(usize, usize)> for Matrix<T, M, N> {
 	fn index_mut(&mut self, coords: (usize, usize)) -> &mut Self::Output {
 		let (i, j) = coords;
-		&mut self.data[Self::to_row_major(i, j)]
+		&mut self.data[i][j]
 	}
 }
 
 // Multiply
 
-impl<T, MA, N, NB> Mul<Matrix<T, N, NB>> for Matrix<T, MA, N>
+impl<T, const MA: usize, const N: usize, const NB: usize> Mul<Matrix<T, N, NB>> for Matrix<T, MA, N>
 where
 	T: Mul + Sum<<T as Mul>::Output> + Copy,
-	MA: Dim,
-	N: Dim,
-	NB: Dim,
 {
 	type Output = Matrix<T, MA, NB>;
 
 	fn mul(self, rhs: Matrix<T, N, NB>) -> Self::Output {
-		let mut new = Matrix::new_uninitialized();
-		for row in self.iter_rows() {
-			let row = row.collect::<Vec<_>>();
-			for col in rhs.iter_cols() {
-				let e = row.iter().zip(col).map(|(r, c)| **r * *c).sum();
-				new.data.push(e);
-			}
-		}
-		new
+		Mul::mul(&self, &rhs)
 	}
 }
 
-impl<T, MA, N, NB> Mul<&Matrix<T, N, NB>> for Matrix<T, MA, N>
+impl<T, const MA: usize, const N: usize, const NB: usize> Mul<&Matrix<T, N, NB>>
+	for Matrix<T, MA, N>
 where
 	T: Mul + Sum<<T as Mul>::Output> + Copy,
-	MA: Dim,
-	N: Dim,
-	NB: Dim,
 {
 	type Output = Matrix<T, MA, NB>;
 
 	fn mul(self, rhs: &Matrix<T, N, NB>) -> Self::Output {
-		let mut new = Matrix::new_uninitialized();
-		for row in self.iter_rows() {
-			let row = row.collect::<Vec<_>>();
-			for col in rhs.iter_cols() {
-				let e = row.iter().zip(col).map(|(r, c)| **r * *c).sum();
-				new.data.push(e);
-			}
-		}
-		new
+		Mul::mul(&self, rhs)
 	}
 }
 
-impl<T, MA, N, NB> Mul<Matrix<T, N, NB>> for &Matrix<T, MA, N>
+impl<T, const MA: usize, const N: usize, const NB: usize> Mul<Matrix<T, N, NB>>
+	for &Matrix<T, MA, N>
 where
 	T: Mul + Sum<<T as Mul>::Output> + Copy,
-	MA: Dim,
-	N: Dim,
-	NB: Dim,
 {
 	type Output = Matrix<T, MA, NB>;
 
 	fn mul(self, rhs: Matrix<T, N, NB>) -> Self::Output {
-		let mut new = Matrix::new_uninitialized();
-		for row in self.iter_rows() {
-			let row = row.collect::<Vec<_>>();
-			for col in rhs.iter_cols() {
-				let e = row.iter().zip(col).map(|(r, c)| **r * *c).sum();
-				new.data.push(e);
-			}
-		}
-		new
+		Mul::mul(self, &rhs)
 	}
 }
 
-impl<T, MA, N, NB> Mul<&Matrix<T, N, NB>> for &Matrix<T, MA, N>
+impl<T, const MA: usize, const N: usize, const NB: usize> Mul<&Matrix<T, N, NB>>
+	for &Matrix<T, MA, N>
 where
 	T: Mul + Sum<<T as Mul>::Output> + Copy,
-	MA: Dim,
-	N: Dim,
-	NB: Dim,
 {
 	type Output = Matrix<T, MA, NB>;
 
 	fn mul(self, rhs: &Matrix<T, N, NB>) -> Self::Output {
-		let mut new = Matrix::new_uninitialized();
-		for row in self.iter_rows() {
-			let row = row.collect::<Vec<_>>();
-			for col in rhs.iter_cols() {
-				let e = row.iter().zip(col).map(|(r, c)| **r * *c).sum();
-				new.data.push(e);
-			}
-		}
-		new
+		Matrix::from_fn(|i, j| {
+			self.iter_row(i)
+				.zip(rhs.iter_col(j))
+				.map(|(r, c)| *r * *c)
+				.sum()
+		})
+	}
+}
+
+// Element-wise arithmetic
+
+impl<T, const M: usize, const N: usize> Add for Matrix<T, M, N>
+where
+	T: Add<Output = T> + Copy,
+{
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Matrix::from_fn(|i, j| self[(i, j)] + rhs[(i, j)])
+	}
+}
+
+impl<T, const M: usize, const N: usize> Sub for Matrix<T, M, N>
+where
+	T: Sub<Output = T> + Copy,
+{
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Matrix::from_fn(|i, j| self[(i, j)] - rhs[(i, j)])
+	}
+}
+
+impl<T, const M: usize, const N: usize> Neg for Matrix<T, M, N>
+where
+	T: Neg<Output = T> + Copy,
+{
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Matrix::from_fn(|i, j| -self[(i, j)])
+	}
+}
+
+impl<T, const M: usize, const N: usize> Mul<T> for Matrix<T, M, N>
+where
+	T: Mul<Output = T> + Copy,
+{
+	type Output = Self;
+
+	fn mul(self, rhs: T) -> Self::Output {
+		Matrix::from_fn(|i, j| self[(i, j)] * rhs)
+	}
+}
+
+impl<T, const M: usize, const N: usize> Div<T> for Matrix<T, M, N>
+where
+	T: Div<Output = T> + Copy,
+{
+	type Output = Self;
+
+	fn div(self, rhs: T) -> Self::Output {
+		Matrix::from_fn(|i, j| self[(i, j)] / rhs)
+	}
+}
+
+impl<T, const M: usize, const N: usize> AddAssign for Matrix<T, M, N>
+where
+	T: AddAssign + Copy,
+{
+	fn add_assign(&mut self, rhs: Self) {
+		self.iter_mut().zip(rhs.iter()).for_each(|(l, r)| *l += *r);
+	}
+}
+
+impl<T, const M: usize, const N: usize> SubAssign for Matrix<T, M, N>
+where
+	T: SubAssign + Copy,
+{
+	fn sub_assign(&mut self, rhs: Self) {
+		self.iter_mut().zip(rhs.iter()).for_each(|(l, r)| *l -= *r);
+	}
+}
+
+impl<T, const M: usize, const N: usize> MulAssign<T> for Matrix<T, M, N>
+where
+	T: MulAssign + Copy,
+{
+	fn mul_assign(&mut self, rhs: T) {
+		self.iter_mut().for_each(|x| *x *= rhs);
+	}
+}
+
+impl<T, const M: usize, const N: usize> DivAssign<T> for Matrix<T, M, N>
+where
+	T: DivAssign + Copy,
+{
+	fn div_assign(&mut self, rhs: T) {
+		self.iter_mut().for_each(|x| *x /= rhs);
+	}
+}
+
+impl<T, const M: usize, const N: usize> Sum for Matrix<T, M, N>
+where
+	T: Add<Output = T> + Zero + Copy,
+{
+	fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+		iter.fold(Matrix::from_fn(|_, _| T::zero()), Add::add)
+	}
+}
+
+impl<'a, T, const M: usize, const N: usize> Sum<&'a Matrix<T, M, N>> for Matrix<T, M, N>
+where
+	T: Add<Output = T> + Zero + Copy,
+{
+	fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+		iter.copied().sum()
+	}
+}
+
+impl<T, const M: usize, const N: usize> Matrix<T, M, N>
+where
+	T: Mul<Output = T> + Copy,
+{
+	/// Element-wise product
+	pub fn hadamard(&self, rhs: &Self) -> Self {
+		Matrix::from_fn(|i, j| self[(i, j)] * rhs[(i, j)])
+	}
+}
+
+impl<T, const M: usize> Matrix<T, M, M>
+where
+	T: Add<Output = T> + Zero + Copy,
+{
+	/// Sum of the elements on the main diagonal
+	pub fn trace(&self) -> T {
+		(0..M).fold(T::zero(), |sum, i| sum + self[(i, i)])
+	}
+}
+
+// Multiply by Tuple
+
+impl<T: Lanes> Mul<Tuple<T>> for Matrix4x4<T> {
+	type Output = Tuple<T>;
+
+	fn mul(self, rhs: Tuple<T>) -> Self::Output {
+		Mul::mul(&self, rhs)
+	}
+}
+
+impl<T: Lanes> Mul<Tuple<T>> for &Matrix4x4<T> {
+	type Output = Tuple<T>;
+
+	fn mul(self, rhs: Tuple<T>) -> Self::Output {
+		T::mul_mat4(&self.data, rhs.to_array()).into()
 	}
 }
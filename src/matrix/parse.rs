@@ -0,0 +1,77 @@
+use std::{fmt, str::FromStr};
+
+use super::Matrix;
+use crate::{error::Error, utils::parse_number};
+
+/// Parses one row per line, either as a pipe-delimited table
+///
+/// ```text
+/// | 1 | 2 |
+/// | 3 | 4 |
+/// ```
+///
+/// or as plain whitespace-separated values. Blank lines are ignored.
+impl<T: FromStr, const M: usize, const N: usize> FromStr for Matrix<T, M, N> {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut values = Vec::with_capacity(M * N);
+		let mut rows = 0;
+		for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+			let cells: Vec<&str> = if line.starts_with('|') {
+				if line.len() < 2 || !line.ends_with('|') {
+					return Err(Error::Parse(format!("unterminated table row `{}`", line)));
+				}
+				line[1..line.len() - 1].split('|').collect()
+			} else {
+				line.split_whitespace().collect()
+			};
+			if cells.len() != N {
+				return Err(Error::Parse(format!(
+					"expected {} columns but row {} has {}",
+					N,
+					rows,
+					cells.len()
+				)));
+			}
+			for cell in cells {
+				values.push(parse_number(cell)?);
+			}
+			rows += 1;
+		}
+		if rows != M {
+			return Err(Error::Parse(format!(
+				"expected {} rows but found {}",
+				M, rows
+			)));
+		}
+
+		let mut values = values.into_iter();
+		Ok(Matrix::from_fn(|_, _| values.next().unwrap()))
+	}
+}
+
+/// Formats as a pipe-delimited table with right-aligned columns
+impl<T: fmt::Display, const M: usize, const N: usize> fmt::Display for Matrix<T, M, N> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let cells: Vec<Vec<String>> = self
+			.data
+			.iter()
+			.map(|row| row.iter().map(|x| x.to_string()).collect())
+			.collect();
+		let widths: Vec<usize> = (0..N)
+			.map(|j| cells.iter().map(|row| row[j].len()).max().unwrap_or(0))
+			.collect();
+
+		for (i, row) in cells.iter().enumerate() {
+			if i > 0 {
+				writeln!(f)?;
+			}
+			write!(f, "|")?;
+			for (cell, width) in row.iter().zip(&widths) {
+				write!(f, " {:>width$} |", cell, width = width)?;
+			}
+		}
+		Ok(())
+	}
+}
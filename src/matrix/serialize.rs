@@ -0,0 +1,29 @@
+use std::convert::TryFrom;
+
+use super::Matrix;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialized as a flat sequence of elements in row-major order
+impl<T, const M: usize, const N: usize> Serialize for Matrix<T, M, N>
+where
+	T: Serialize,
+{
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_seq(self.iter())
+	}
+}
+
+/// Deserialized from a flat sequence of exactly `M * N` elements in row-major order
+impl<'de, T, const M: usize, const N: usize> Deserialize<'de> for Matrix<T, M, N>
+where
+	T: Deserialize<'de>,
+{
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let data = Vec::<T>::deserialize(deserializer)?;
+		let len = data.len();
+		Matrix::try_from(data).map_err(|_| {
+			let expected = format!("{} elements for a {}x{} matrix", M * N, M, N);
+			D::Error::invalid_length(len, &expected.as_str())
+		})
+	}
+}
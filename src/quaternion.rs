@@ -0,0 +1,203 @@
+use std::ops;
+
+use crate::{
+	matrix,
+	matrix::Matrix4x4,
+	tuple::Tuple,
+	utils::{clamp, ApproxEq, Tolerance, EPSILON},
+};
+
+/// A rotation represented as a unit quaternion `w + xi + yj + zk`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion {
+	pub w: f64,
+	pub x: f64,
+	pub y: f64,
+	pub z: f64,
+}
+
+impl Quaternion {
+	pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+		Self { w, x, y, z }
+	}
+
+	/// The rotation that does nothing
+	pub fn identity() -> Self {
+		Self::new(1., 0., 0., 0.)
+	}
+
+	/// Rotation by `angle` radians around `axis`, following the same
+	/// handedness as `rotation_x`, `rotation_y` and `rotation_z`
+	pub fn from_axis_angle(axis: Tuple, angle: f64) -> Self {
+		let axis = axis.normalize();
+		let (sin, cos) = (angle / 2.).sin_cos();
+		Self::new(cos, axis.x * sin, axis.y * sin, axis.z * sin)
+	}
+
+	/// Returns the normalized rotation axis and the angle in radians.
+	/// The axis of a rotation by 0 is arbitrarily chosen as the x axis.
+	pub fn to_axis_angle(&self) -> (Tuple, f64) {
+		let q = self.normalize();
+		let angle = 2. * clamp(q.w, -1., 1.).acos();
+		let s = (1. - q.w * q.w).max(0.).sqrt();
+		if s < EPSILON {
+			(Tuple::vector(1., 0., 0.), angle)
+		} else {
+			(Tuple::vector(q.x / s, q.y / s, q.z / s), angle)
+		}
+	}
+
+	/// Extract the rotation from the upper-left 3x3 block of a rotation matrix
+	pub fn from_matrix(m: &Matrix4x4<f64>) -> Self {
+		let trace = m[(0, 0)] + m[(1, 1)] + m[(2, 2)];
+		let q = if trace > 0. {
+			let s = (trace + 1.).sqrt() * 2.;
+			Self::new(
+				s / 4.,
+				(m[(2, 1)] - m[(1, 2)]) / s,
+				(m[(0, 2)] - m[(2, 0)]) / s,
+				(m[(1, 0)] - m[(0, 1)]) / s,
+			)
+		} else if m[(0, 0)] > m[(1, 1)] && m[(0, 0)] > m[(2, 2)] {
+			let s = (1. + m[(0, 0)] - m[(1, 1)] - m[(2, 2)]).sqrt() * 2.;
+			Self::new(
+				(m[(2, 1)] - m[(1, 2)]) / s,
+				s / 4.,
+				(m[(0, 1)] + m[(1, 0)]) / s,
+				(m[(0, 2)] + m[(2, 0)]) / s,
+			)
+		} else if m[(1, 1)] > m[(2, 2)] {
+			let s = (1. + m[(1, 1)] - m[(0, 0)] - m[(2, 2)]).sqrt() * 2.;
+			Self::new(
+				(m[(0, 2)] - m[(2, 0)]) / s,
+				(m[(0, 1)] + m[(1, 0)]) / s,
+				s / 4.,
+				(m[(1, 2)] + m[(2, 1)]) / s,
+			)
+		} else {
+			let s = (1. + m[(2, 2)] - m[(0, 0)] - m[(1, 1)]).sqrt() * 2.;
+			Self::new(
+				(m[(1, 0)] - m[(0, 1)]) / s,
+				(m[(0, 2)] + m[(2, 0)]) / s,
+				(m[(1, 2)] + m[(2, 1)]) / s,
+				s / 4.,
+			)
+		};
+		q.normalize()
+	}
+
+	pub fn to_matrix(&self) -> Matrix4x4<f64> {
+		let Quaternion { w, x, y, z } = self.normalize();
+		matrix![ 4, 4 =>
+			1. - 2. * (y * y + z * z), 2. * (x * y - w * z),      2. * (x * z + w * y),      0.;
+			2. * (x * y + w * z),      1. - 2. * (x * x + z * z), 2. * (y * z - w * x),      0.;
+			2. * (x * z - w * y),      2. * (y * z + w * x),      1. - 2. * (x * x + y * y), 0.;
+			0.,                        0.,                        0.,                        1.;
+		]
+	}
+
+	pub fn magnitude(&self) -> f64 {
+		self.dot(self).sqrt()
+	}
+
+	pub fn normalize(&self) -> Self {
+		let m = self.magnitude();
+		Self::new(self.w / m, self.x / m, self.y / m, self.z / m)
+	}
+
+	/// The inverse rotation of a unit quaternion
+	pub fn conjugate(&self) -> Self {
+		Self::new(self.w, -self.x, -self.y, -self.z)
+	}
+
+	pub fn dot(&self, q: &Quaternion) -> f64 {
+		self.w * q.w + self.x * q.x + self.y * q.y + self.z * q.z
+	}
+
+	/// Rotate a point or vector
+	pub fn rotate(&self, t: Tuple) -> Tuple {
+		let q = self.normalize();
+		let p = q * Quaternion::new(0., t.x, t.y, t.z) * q.conjugate();
+		Tuple::new(p.x, p.y, p.z, t.w)
+	}
+
+	/// Spherical linear interpolation, moving from `self` at `t = 0` to `other` at `t = 1`
+	/// with constant angular velocity along the shortest path
+	pub fn slerp(&self, other: &Quaternion, t: f64) -> Self {
+		let a = self.normalize();
+		let mut b = other.normalize();
+		let mut dot = a.dot(&b);
+		// q and -q are the same rotation, take the shorter way around
+		if dot < 0. {
+			b = -b;
+			dot = -dot;
+		}
+		// almost identical rotations, fall back to linear interpolation to avoid dividing by sin(0)
+		if dot > 1. - EPSILON {
+			return (a * (1. - t) + b * t).normalize();
+		}
+		let theta = dot.acos();
+		let sin = theta.sin();
+		a * (((1. - t) * theta).sin() / sin) + b * ((t * theta).sin() / sin)
+	}
+}
+
+impl ApproxEq for Quaternion {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.w.approx_eq_with(other.w, tolerance)
+			&& self.x.approx_eq_with(other.x, tolerance)
+			&& self.y.approx_eq_with(other.y, tolerance)
+			&& self.z.approx_eq_with(other.z, tolerance)
+	}
+}
+
+/// Composition, `(a * b)` rotates by `b` first and then by `a`
+impl ops::Mul<Quaternion> for Quaternion {
+	type Output = Quaternion;
+	fn mul(self, rhs: Quaternion) -> Quaternion {
+		Quaternion::new(
+			self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+			self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+			self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+			self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+		)
+	}
+}
+
+impl ops::Add<Quaternion> for Quaternion {
+	type Output = Quaternion;
+	fn add(self, rhs: Quaternion) -> Quaternion {
+		Quaternion::new(
+			self.w + rhs.w,
+			self.x + rhs.x,
+			self.y + rhs.y,
+			self.z + rhs.z,
+		)
+	}
+}
+
+impl ops::Neg for Quaternion {
+	type Output = Quaternion;
+	fn neg(self) -> Quaternion {
+		Quaternion::new(-self.w, -self.x, -self.y, -self.z)
+	}
+}
+
+impl ops::Mul<f64> for Quaternion {
+	type Output = Quaternion;
+	fn mul(self, rhs: f64) -> Quaternion {
+		Quaternion::new(self.w * rhs, self.x * rhs, self.y * rhs, self.z * rhs)
+	}
+}
+
+impl From<Quaternion> for Matrix4x4<f64> {
+	fn from(q: Quaternion) -> Self {
+		q.to_matrix()
+	}
+}
+
+impl From<&Matrix4x4<f64>> for Quaternion {
+	fn from(m: &Matrix4x4<f64>) -> Self {
+		Quaternion::from_matrix(m)
+	}
+}
@@ -0,0 +1,33 @@
+use crate::{
+	geometry::{Point3, Vector3},
+	matrix::Matrix4x4,
+	utils::{ApproxEq, Tolerance},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ray {
+	pub origin: Point3,
+	pub direction: Vector3,
+}
+
+impl Ray {
+	pub fn new(origin: Point3, direction: Vector3) -> Self {
+		Self { origin, direction }
+	}
+
+	/// The point at distance `t` along the ray
+	pub fn position(&self, t: f64) -> Point3 {
+		self.origin + self.direction * t
+	}
+
+	pub fn transform(&self, m: &Matrix4x4<f64>) -> Self {
+		Ray::new(m * self.origin, m * self.direction)
+	}
+}
+
+impl ApproxEq for Ray {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.origin.approx_eq_with(other.origin, tolerance)
+			&& self.direction.approx_eq_with(other.direction, tolerance)
+	}
+}
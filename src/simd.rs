@@ -0,0 +1,249 @@
+//! Four-lane arithmetic backing `Tuple`, `Color` and matrix-tuple products.
+//! With the `simd` feature on x86_64 the lanes are processed with SSE/SSE2,
+//! which every x86_64 CPU supports. Everywhere else plain scalar code is used.
+
+use num_traits::Float;
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+use std::arch::x86_64::*;
+
+/// Floating-point types that provide (possibly vectorized) four-lane operations.
+/// The provided methods are the scalar fallback from `scalar`.
+pub trait Lanes: Float {
+	fn add4(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+		scalar::add4(a, b)
+	}
+
+	fn sub4(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+		scalar::sub4(a, b)
+	}
+
+	/// Lane-wise product
+	fn mul4(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+		scalar::mul4(a, b)
+	}
+
+	fn scale4(a: [Self; 4], s: Self) -> [Self; 4] {
+		scalar::scale4(a, s)
+	}
+
+	fn dot4(a: [Self; 4], b: [Self; 4]) -> Self {
+		scalar::dot4(a, b)
+	}
+
+	/// Cross product of the first three lanes, the fourth lane of the result is 0
+	fn cross3(a: [Self; 4], b: [Self; 4]) -> [Self; 4] {
+		scalar::cross3(a, b)
+	}
+
+	/// Product of a row-major 4x4 matrix and a column vector
+	fn mul_mat4(m: &[[Self; 4]; 4], v: [Self; 4]) -> [Self; 4] {
+		[
+			Self::dot4(m[0], v),
+			Self::dot4(m[1], v),
+			Self::dot4(m[2], v),
+			Self::dot4(m[3], v),
+		]
+	}
+}
+
+/// Plain scalar versions of the `Lanes` operations, available with or without
+/// the `simd` feature so the vectorized code can be checked against them
+pub mod scalar {
+	use num_traits::Float;
+
+	pub fn add4<T: Float>(a: [T; 4], b: [T; 4]) -> [T; 4] {
+		[a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]]
+	}
+
+	pub fn sub4<T: Float>(a: [T; 4], b: [T; 4]) -> [T; 4] {
+		[a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]]
+	}
+
+	pub fn mul4<T: Float>(a: [T; 4], b: [T; 4]) -> [T; 4] {
+		[a[0] * b[0], a[1] * b[1], a[2] * b[2], a[3] * b[3]]
+	}
+
+	pub fn scale4<T: Float>(a: [T; 4], s: T) -> [T; 4] {
+		[a[0] * s, a[1] * s, a[2] * s, a[3] * s]
+	}
+
+	pub fn dot4<T: Float>(a: [T; 4], b: [T; 4]) -> T {
+		a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+	}
+
+	pub fn cross3<T: Float>(a: [T; 4], b: [T; 4]) -> [T; 4] {
+		[
+			a[1] * b[2] - a[2] * b[1],
+			a[2] * b[0] - a[0] * b[2],
+			a[0] * b[1] - a[1] * b[0],
+			T::zero(),
+		]
+	}
+
+	pub fn mul_mat4<T: Float>(m: &[[T; 4]; 4], v: [T; 4]) -> [T; 4] {
+		[dot4(m[0], v), dot4(m[1], v), dot4(m[2], v), dot4(m[3], v)]
+	}
+}
+
+impl Lanes for f32 {
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn add4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+		let mut out = [0.; 4];
+		unsafe { _mm_storeu_ps(out.as_mut_ptr(), _mm_add_ps(load_ps(&a), load_ps(&b))) };
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn sub4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+		let mut out = [0.; 4];
+		unsafe { _mm_storeu_ps(out.as_mut_ptr(), _mm_sub_ps(load_ps(&a), load_ps(&b))) };
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn mul4(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+		let mut out = [0.; 4];
+		unsafe { _mm_storeu_ps(out.as_mut_ptr(), _mm_mul_ps(load_ps(&a), load_ps(&b))) };
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn scale4(a: [f32; 4], s: f32) -> [f32; 4] {
+		let mut out = [0.; 4];
+		unsafe { _mm_storeu_ps(out.as_mut_ptr(), _mm_mul_ps(load_ps(&a), _mm_set1_ps(s))) };
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn dot4(a: [f32; 4], b: [f32; 4]) -> f32 {
+		unsafe {
+			let m = _mm_mul_ps(load_ps(&a), load_ps(&b));
+			let shuffled = _mm_shuffle_ps::<SWAP_PAIRS>(m, m);
+			// [m0 + m1, _, m2 + m3, _]
+			let sums = _mm_add_ps(m, shuffled);
+			let high = _mm_movehl_ps(shuffled, sums);
+			_mm_cvtss_f32(_mm_add_ss(sums, high))
+		}
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn cross3(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+		let mut out = [0.; 4];
+		unsafe {
+			let a = load_ps(&a);
+			let b = load_ps(&b);
+			let a_yzx = _mm_shuffle_ps::<ROTATE_YZX>(a, a);
+			let b_yzx = _mm_shuffle_ps::<ROTATE_YZX>(b, b);
+			let c = _mm_sub_ps(_mm_mul_ps(a, b_yzx), _mm_mul_ps(a_yzx, b));
+			let c = _mm_shuffle_ps::<ROTATE_YZX>(c, c);
+			// the w lane is w * w - w * w, clear it so infinities don't leak into it
+			let c = _mm_and_ps(c, _mm_castsi128_ps(_mm_set_epi32(0, -1, -1, -1)));
+			_mm_storeu_ps(out.as_mut_ptr(), c);
+		}
+		out
+	}
+}
+
+impl Lanes for f64 {
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn add4(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+		let mut out = [0.; 4];
+		unsafe {
+			let (a0, a1) = load_pd(&a);
+			let (b0, b1) = load_pd(&b);
+			store_pd(&mut out, _mm_add_pd(a0, b0), _mm_add_pd(a1, b1));
+		}
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn sub4(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+		let mut out = [0.; 4];
+		unsafe {
+			let (a0, a1) = load_pd(&a);
+			let (b0, b1) = load_pd(&b);
+			store_pd(&mut out, _mm_sub_pd(a0, b0), _mm_sub_pd(a1, b1));
+		}
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn mul4(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+		let mut out = [0.; 4];
+		unsafe {
+			let (a0, a1) = load_pd(&a);
+			let (b0, b1) = load_pd(&b);
+			store_pd(&mut out, _mm_mul_pd(a0, b0), _mm_mul_pd(a1, b1));
+		}
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn scale4(a: [f64; 4], s: f64) -> [f64; 4] {
+		let mut out = [0.; 4];
+		unsafe {
+			let (a0, a1) = load_pd(&a);
+			let s = _mm_set1_pd(s);
+			store_pd(&mut out, _mm_mul_pd(a0, s), _mm_mul_pd(a1, s));
+		}
+		out
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn dot4(a: [f64; 4], b: [f64; 4]) -> f64 {
+		unsafe {
+			let (a0, a1) = load_pd(&a);
+			let (b0, b1) = load_pd(&b);
+			// [a0 * b0 + a2 * b2, a1 * b1 + a3 * b3]
+			let m = _mm_add_pd(_mm_mul_pd(a0, b0), _mm_mul_pd(a1, b1));
+			_mm_cvtsd_f64(_mm_add_sd(m, _mm_unpackhi_pd(m, m)))
+		}
+	}
+
+	#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+	fn cross3(a: [f64; 4], b: [f64; 4]) -> [f64; 4] {
+		let mut out = [0.; 4];
+		unsafe {
+			// x and y of the result side by side, z on its own
+			let xy = _mm_sub_pd(
+				_mm_mul_pd(_mm_set_pd(a[2], a[1]), _mm_set_pd(b[0], b[2])),
+				_mm_mul_pd(_mm_set_pd(a[0], a[2]), _mm_set_pd(b[2], b[1])),
+			);
+			let z = _mm_set_sd(a[0] * b[1] - a[1] * b[0]);
+			store_pd(&mut out, xy, z);
+		}
+		out
+	}
+}
+
+/// Shuffle mask listing the source lanes from the highest to the lowest, like `_MM_SHUFFLE`
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+const fn shuffle_mask(z: i32, y: i32, x: i32, w: i32) -> i32 {
+	(z << 6) | (y << 4) | (x << 2) | w
+}
+
+/// (x, y, z, w) to (y, x, w, z)
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+const SWAP_PAIRS: i32 = shuffle_mask(2, 3, 0, 1);
+
+/// (x, y, z, w) to (y, z, x, w)
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+const ROTATE_YZX: i32 = shuffle_mask(3, 0, 2, 1);
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+unsafe fn load_ps(a: &[f32; 4]) -> __m128 {
+	_mm_loadu_ps(a.as_ptr())
+}
+
+/// Load four doubles as the low and high halves
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+unsafe fn load_pd(a: &[f64; 4]) -> (__m128d, __m128d) {
+	(_mm_loadu_pd(a.as_ptr()), _mm_loadu_pd(a.as_ptr().add(2)))
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+unsafe fn store_pd(out: &mut [f64; 4], low: __m128d, high: __m128d) {
+	_mm_storeu_pd(out.as_mut_ptr(), low);
+	_mm_storeu_pd(out.as_mut_ptr().add(2), high);
+}
@@ -0,0 +1,99 @@
+use std::ops::Mul;
+
+use crate::{
+	geometry::{Normal3, Point3, Vector3},
+	matrix::{Matrix, Matrix4x4},
+	ray::Ray,
+};
+
+/// A transformation matrix together with its inverse and inverse transpose.
+/// Both are computed once on construction, so applying the transform
+/// to rays and normals never has to invert the matrix again.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transform {
+	matrix: Matrix4x4<f64>,
+	inverse: Matrix4x4<f64>,
+	inverse_transpose: Matrix4x4<f64>,
+}
+
+impl Transform {
+	/// Returns `None` if the matrix is not invertible
+	pub fn new(matrix: Matrix4x4<f64>) -> Option<Self> {
+		matrix.invert_fast().map(|inverse| Self {
+			matrix,
+			inverse,
+			inverse_transpose: inverse.transpose(),
+		})
+	}
+
+	pub fn identity() -> Self {
+		Self {
+			matrix: Matrix::identity(),
+			inverse: Matrix::identity(),
+			inverse_transpose: Matrix::identity(),
+		}
+	}
+
+	pub fn matrix(&self) -> &Matrix4x4<f64> {
+		&self.matrix
+	}
+
+	pub fn inverse(&self) -> &Matrix4x4<f64> {
+		&self.inverse
+	}
+
+	pub fn inverse_transpose(&self) -> &Matrix4x4<f64> {
+		&self.inverse_transpose
+	}
+
+	/// The inverse transform, obtained without recomputing anything
+	pub fn invert(&self) -> Self {
+		Self {
+			matrix: self.inverse,
+			inverse: self.matrix,
+			inverse_transpose: self.matrix.transpose(),
+		}
+	}
+
+	pub fn point(&self, p: Point3) -> Point3 {
+		self.matrix * p
+	}
+
+	pub fn vector(&self, v: Vector3) -> Vector3 {
+		self.matrix * v
+	}
+
+	pub fn normal(&self, n: Normal3) -> Normal3 {
+		n.transform(&self.inverse_transpose)
+	}
+
+	pub fn ray(&self, r: &Ray) -> Ray {
+		r.transform(&self.matrix)
+	}
+
+	/// Apply the inverse transform to a ray, e.g. to move it from world into object space
+	pub fn inverse_ray(&self, r: &Ray) -> Ray {
+		r.transform(&self.inverse)
+	}
+}
+
+impl Default for Transform {
+	fn default() -> Self {
+		Self::identity()
+	}
+}
+
+/// Composition, `(a * b)` applies `b` first and then `a`.
+/// The inverse is composed from the cached inverses as `b⁻¹ * a⁻¹`.
+impl Mul<Transform> for Transform {
+	type Output = Transform;
+
+	fn mul(self, rhs: Transform) -> Self::Output {
+		let inverse = rhs.inverse * self.inverse;
+		Transform {
+			matrix: self.matrix * rhs.matrix,
+			inverse,
+			inverse_transpose: inverse.transpose(),
+		}
+	}
+}
@@ -0,0 +1,109 @@
+use crate::{matrix, matrix::*, tuple::Tuple};
+
+/// Create a matrix that moves a point by (x, y, z).
+/// Vectors are not affected by translation.
+pub fn translation(x: f64, y: f64, z: f64) -> Matrix4x4<f64> {
+	matrix![ 4, 4 =>
+		1., 0., 0., x;
+		0., 1., 0., y;
+		0., 0., 1., z;
+		0., 0., 0., 1.;
+	]
+}
+
+/// Create a matrix that scales each component by the given factor
+pub fn scaling(x: f64, y: f64, z: f64) -> Matrix4x4<f64> {
+	matrix![ 4, 4 =>
+		x,  0., 0., 0.;
+		0., y,  0., 0.;
+		0., 0., z,  0.;
+		0., 0., 0., 1.;
+	]
+}
+
+/// Create a matrix that rotates by `r` radians around the x axis
+pub fn rotation_x(r: f64) -> Matrix4x4<f64> {
+	let (sin, cos) = r.sin_cos();
+	matrix![ 4, 4 =>
+		1., 0.,   0.,  0.;
+		0., cos, -sin, 0.;
+		0., sin,  cos, 0.;
+		0., 0.,   0.,  1.;
+	]
+}
+
+/// Create a matrix that rotates by `r` radians around the y axis
+pub fn rotation_y(r: f64) -> Matrix4x4<f64> {
+	let (sin, cos) = r.sin_cos();
+	matrix![ 4, 4 =>
+		 cos, 0., sin, 0.;
+		 0.,  1., 0.,  0.;
+		-sin, 0., cos, 0.;
+		 0.,  0., 0.,  1.;
+	]
+}
+
+/// Create a matrix that rotates by `r` radians around the z axis
+pub fn rotation_z(r: f64) -> Matrix4x4<f64> {
+	let (sin, cos) = r.sin_cos();
+	matrix![ 4, 4 =>
+		cos, -sin, 0., 0.;
+		sin,  cos, 0., 0.;
+		0.,   0.,  1., 0.;
+		0.,   0.,  0., 1.;
+	]
+}
+
+/// Create a matrix that moves each component in proportion to the other two.
+/// `xy` is the amount x moves in proportion to y, and so on.
+pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix4x4<f64> {
+	matrix![ 4, 4 =>
+		1., xy, xz, 0.;
+		yx, 1., yz, 0.;
+		zx, zy, 1., 0.;
+		0., 0., 0., 1.;
+	]
+}
+
+/// Create a matrix that orients the world relative to an eye at `from`, looking at `to`.
+/// `up` only needs to point roughly upwards.
+pub fn view_transform(from: Tuple, to: Tuple, up: Tuple) -> Matrix4x4<f64> {
+	let forward = (to - from).normalize();
+	let left = forward.cross(&up.normalize());
+	let true_up = left.cross(&forward);
+	let orientation = matrix![ 4, 4 =>
+		 left.x,     left.y,     left.z,    0.;
+		 true_up.x,  true_up.y,  true_up.z, 0.;
+		-forward.x, -forward.y, -forward.z, 0.;
+		 0.,         0.,         0.,        1.;
+	];
+	orientation * translation(-from.x, -from.y, -from.z)
+}
+
+/// Chainable transformations, applied in reading order:
+/// `Matrix::identity().rotate_x(r).scale(x, y, z)` first rotates and then scales.
+impl Matrix4x4<f64> {
+	pub fn translate(self, x: f64, y: f64, z: f64) -> Self {
+		translation(x, y, z) * self
+	}
+
+	pub fn scale(self, x: f64, y: f64, z: f64) -> Self {
+		scaling(x, y, z) * self
+	}
+
+	pub fn rotate_x(self, r: f64) -> Self {
+		rotation_x(r) * self
+	}
+
+	pub fn rotate_y(self, r: f64) -> Self {
+		rotation_y(r) * self
+	}
+
+	pub fn rotate_z(self, r: f64) -> Self {
+		rotation_z(r) * self
+	}
+
+	pub fn shear(self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+		shearing(xy, xz, yx, yz, zx, zy) * self
+	}
+}
@@ -1,102 +1,104 @@
-use std::ops;
-
-use crate::matrix::*;
-
-#[derive(Debug, Copy, Clone)]
-pub struct Tuple {
-	pub x: f64,
-	pub y: f64,
-	pub z: f64,
-	pub w: f64,
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{fmt, ops, str::FromStr};
+
+use crate::{
+	error::Error,
+	matrix::*,
+	simd::Lanes,
+	utils::{parse_call, ApproxEq, Tolerance},
+};
+
+/// A point (w = 1) or vector (w = 0), generic over the floating-point precision
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tuple<T = f64> {
+	pub x: T,
+	pub y: T,
+	pub z: T,
+	pub w: T,
 }
 
-impl Tuple {
-	pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+impl<T: Lanes> Tuple<T> {
+	pub fn new(x: T, y: T, z: T, w: T) -> Self {
 		Self { x, y, z, w }
 	}
 
-	pub fn point(x: f64, y: f64, z: f64) -> Self {
-		Tuple::new(x, y, z, 1.)
+	pub fn point(x: T, y: T, z: T) -> Self {
+		Tuple::new(x, y, z, T::one())
 	}
 
-	pub fn vector(x: f64, y: f64, z: f64) -> Self {
-		Tuple::new(x, y, z, 0.)
+	pub fn vector(x: T, y: T, z: T) -> Self {
+		Tuple::new(x, y, z, T::zero())
 	}
 
 	pub fn is_point(&self) -> bool {
-		self.w == 1.
+		self.w == T::one()
 	}
 
 	pub fn is_vector(&self) -> bool {
-		self.w == 0.
+		self.w == T::zero()
 	}
 
-	pub fn magnitude(&self) -> f64 {
-		(self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+	pub fn magnitude(&self) -> T {
+		let xyz = [self.x, self.y, self.z, T::zero()];
+		T::dot4(xyz, xyz).sqrt()
 	}
 
-	pub fn normalize(&self) -> Tuple {
+	pub fn normalize(&self) -> Self {
 		let m = self.magnitude();
 		Tuple::vector(self.x / m, self.y / m, self.z / m)
 	}
 
-	pub fn dot(&self, t: &Tuple) -> f64 {
-		self.x * t.x + self.y * t.y + self.z * t.z + self.w * t.w
+	pub fn dot(&self, t: &Self) -> T {
+		T::dot4(self.to_array(), t.to_array())
 	}
 
-	pub fn cross(&self, t: &Tuple) -> Self {
-		Tuple::vector(
-			self.y * t.z - self.z * t.y,
-			self.z * t.x - self.x * t.z,
-			self.x * t.y - self.y * t.x,
-		)
+	pub fn cross(&self, t: &Self) -> Self {
+		T::cross3(self.to_array(), t.to_array()).into()
 	}
 
-	pub fn reflect(&self, normal: Tuple) -> Self {
-		*self - normal * 2. * self.dot(&normal)
+	pub fn reflect(&self, normal: Self) -> Self {
+		*self - normal * (T::one() + T::one()) * self.dot(&normal)
 	}
 
-	pub fn to_matrix(self) -> Matrix<f64, M4, M1> {
-		vec![self.x, self.y, self.z, self.w].into()
+	pub fn to_array(&self) -> [T; 4] {
+		[self.x, self.y, self.z, self.w]
+	}
+
+	pub fn to_matrix(self) -> Matrix<T, 4, 1> {
+		let data = [self.x, self.y, self.z, self.w];
+		Matrix::from_fn(|i, _| data[i])
 	}
 }
 
-impl PartialEq for Tuple {
-	fn eq(&self, other: &Self) -> bool {
-		super::approx_eq(self.x, other.x)
-			&& super::approx_eq(self.y, other.y)
-			&& super::approx_eq(self.z, other.z)
-			&& self.w == other.w
+impl<T: ApproxEq> ApproxEq for Tuple<T> {
+	fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+		self.x.approx_eq_with(other.x, tolerance)
+			&& self.y.approx_eq_with(other.y, tolerance)
+			&& self.z.approx_eq_with(other.z, tolerance)
+			&& self.w.approx_eq_with(other.w, tolerance)
 	}
 }
 
-impl ops::Add<Tuple> for Tuple {
-	type Output = Tuple;
-	fn add(self, rhs: Tuple) -> Tuple {
-		Tuple {
-			x: self.x + rhs.x,
-			y: self.y + rhs.y,
-			z: self.z + rhs.z,
-			w: self.w + rhs.w,
-		}
+impl<T: Lanes> ops::Add<Tuple<T>> for Tuple<T> {
+	type Output = Tuple<T>;
+	fn add(self, rhs: Tuple<T>) -> Tuple<T> {
+		T::add4(self.to_array(), rhs.to_array()).into()
 	}
 }
 
-impl ops::Sub<Tuple> for Tuple {
-	type Output = Tuple;
-	fn sub(self, rhs: Tuple) -> Tuple {
-		Tuple {
-			x: self.x - rhs.x,
-			y: self.y - rhs.y,
-			z: self.z - rhs.z,
-			w: self.w - rhs.w,
-		}
+impl<T: Lanes> ops::Sub<Tuple<T>> for Tuple<T> {
+	type Output = Tuple<T>;
+	fn sub(self, rhs: Tuple<T>) -> Tuple<T> {
+		T::sub4(self.to_array(), rhs.to_array()).into()
 	}
 }
 
-impl ops::Neg for Tuple {
-	type Output = Tuple;
-	fn neg(self) -> Tuple {
+impl<T: Float> ops::Neg for Tuple<T> {
+	type Output = Tuple<T>;
+	fn neg(self) -> Tuple<T> {
 		Tuple {
 			x: -self.x,
 			y: -self.y,
@@ -106,21 +108,16 @@ impl ops::Neg for Tuple {
 	}
 }
 
-impl ops::Mul<f64> for Tuple {
-	type Output = Tuple;
-	fn mul(self, rhs: f64) -> Tuple {
-		Tuple {
-			x: self.x * rhs,
-			y: self.y * rhs,
-			z: self.z * rhs,
-			w: self.w * rhs,
-		}
+impl<T: Lanes> ops::Mul<T> for Tuple<T> {
+	type Output = Tuple<T>;
+	fn mul(self, rhs: T) -> Tuple<T> {
+		T::scale4(self.to_array(), rhs).into()
 	}
 }
 
-impl ops::Div<f64> for Tuple {
-	type Output = Tuple;
-	fn div(self, rhs: f64) -> Tuple {
+impl<T: Float> ops::Div<T> for Tuple<T> {
+	type Output = Tuple<T>;
+	fn div(self, rhs: T) -> Tuple<T> {
 		Tuple {
 			x: self.x / rhs,
 			y: self.y / rhs,
@@ -130,13 +127,40 @@ impl ops::Div<f64> for Tuple {
 	}
 }
 
-impl From<[f64; 4]> for Tuple {
-	fn from(item: [f64; 4]) -> Self {
-		Tuple {
-			x: item[0],
-			y: item[1],
-			z: item[2],
-			w: item[3],
+impl<T> From<[T; 4]> for Tuple<T> {
+	fn from(item: [T; 4]) -> Self {
+		let [x, y, z, w] = item;
+		Tuple { x, y, z, w }
+	}
+}
+
+/// Parses `point(x, y, z)`, `vector(x, y, z)` or `tuple(x, y, z, w)`
+impl<T: Lanes + FromStr> FromStr for Tuple<T> {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (name, args) = parse_call(s)?;
+		match (name, args.as_slice()) {
+			("point", &[x, y, z]) => Ok(Tuple::point(x, y, z)),
+			("vector", &[x, y, z]) => Ok(Tuple::vector(x, y, z)),
+			("tuple", &[x, y, z, w]) => Ok(Tuple::new(x, y, z, w)),
+			_ => Err(Error::Parse(format!(
+				"expected point(x, y, z), vector(x, y, z) or tuple(x, y, z, w), found `{}`",
+				s.trim()
+			))),
+		}
+	}
+}
+
+/// Formats as `point(..)` or `vector(..)` when possible, `tuple(..)` otherwise
+impl<T: Lanes + fmt::Display> fmt::Display for Tuple<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_point() {
+			write!(f, "point({}, {}, {})", self.x, self.y, self.z)
+		} else if self.is_vector() {
+			write!(f, "vector({}, {}, {})", self.x, self.y, self.z)
+		} else {
+			write!(f, "tuple({}, {}, {}, {})", self.x, self.y, self.z, self.w)
 		}
 	}
 }
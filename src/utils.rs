@@ -1,4 +1,6 @@
-use std::convert::TryInto;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
 
 /// Clamp function, see https://github.com/rust-lang/rust/issues/44095
 /// Unstable as of writing this (23.08.19)
@@ -35,65 +37,154 @@ pub fn split_long_lines(max_length: usize, s: &str) -> Vec<String> {
 	buf
 }
 
-/// Clamp the value to the range from 0.0 to 1.0 and then map that range onto 0 to max
+/// Clamp the value to the range from 0.0 to 1.0 and then map that range onto 0 to max.
+/// NaN maps to 0.
 pub fn clamp_and_normalize(num: f64, max: usize) -> usize {
-	((clamp(num, 0.0, 1.0) * max as f64).round() as i64)
-		.try_into()
-		.unwrap()
+	(clamp(num, 0.0, 1.0) * max as f64).round() as usize
 }
 
-/// Trait that provides equivalence for floating-point based types
-pub trait ApproxEq<Rhs = Self> {
-	/// Maximum allowed error such that two instances are regarded as being equal.
-	fn approx_eq(self, other: Rhs) -> bool;
+/// Parse a single number, reporting failures as `Error::Parse`
+pub fn parse_number<T: FromStr>(s: &str) -> Result<T> {
+	let s = s.trim();
+	s.parse()
+		.map_err(|_| Error::Parse(format!("invalid number `{}`", s)))
+}
+
+/// Split a call such as `point(1, 2, 3)` into its name and parsed arguments
+pub fn parse_call<T: FromStr>(s: &str) -> Result<(&str, Vec<T>)> {
+	let s = s.trim();
+	let open = s
+		.find('(')
+		.filter(|_| s.ends_with(')'))
+		.ok_or_else(|| Error::Parse(format!("expected `name(...)`, found `{}`", s)))?;
+	let args = s[open + 1..s.len() - 1]
+		.split(',')
+		.map(parse_number)
+		.collect::<Result<Vec<T>>>()?;
+	Ok((s[..open].trim(), args))
 }
 
-pub const EPSILON_F64: f64 = 0.1e-4;
-impl ApproxEq for f64 {
-	fn approx_eq(self, other: Self) -> bool {
-		(self - other).abs() < EPSILON_F64
+/// Default absolute tolerance used by `ApproxEq::approx_eq`
+pub const EPSILON: f64 = 0.00001;
+
+#[deprecated(note = "use `EPSILON` or `Tolerance::Absolute`")]
+pub const EPSILON_F64: f64 = EPSILON;
+#[deprecated(note = "use `EPSILON` or `Tolerance::Absolute`")]
+pub const EPSILON_F32: f32 = EPSILON as f32;
+
+/// How far apart two floating-point values may be and still be regarded as equal
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tolerance {
+	/// `|a - b| < epsilon`
+	Absolute(f64),
+	/// `|a - b| <= epsilon * max(|a|, |b|)`
+	Relative(f64),
+	/// At most this many representable values lie between a and b
+	Ulps(u64),
+}
+
+impl Default for Tolerance {
+	fn default() -> Self {
+		Tolerance::Absolute(EPSILON)
 	}
 }
 
-pub const EPSILON_F32: f32 = 0.1e-4;
-impl ApproxEq for f32 {
-	fn approx_eq(self, other: Self) -> bool {
-		(self - other).abs() < EPSILON_F32
+/// Trait that provides equivalence for floating-point based types
+pub trait ApproxEq<Rhs = Self> {
+	/// Whether `self` and `other` are equal within the given tolerance.
+	/// Composite types compare component-wise.
+	fn approx_eq_with(self, other: Rhs, tolerance: Tolerance) -> bool;
+
+	/// Whether `self` and `other` are equal within the default absolute tolerance `EPSILON`
+	fn approx_eq(self, other: Rhs) -> bool
+	where
+		Self: Sized,
+	{
+		self.approx_eq_with(other, Tolerance::default())
 	}
 }
 
-/// Adaption of assert_eq from the stdlib to work with assert_eq rather than std::ops::Eq::eq
+macro_rules! impl_approx_eq_float {
+	($float:ty, $int:ty) => {
+		impl ApproxEq for $float {
+			fn approx_eq_with(self, other: Self, tolerance: Tolerance) -> bool {
+				// also covers infinities of the same sign
+				if self == other {
+					return true;
+				}
+				let diff = (self - other).abs();
+				match tolerance {
+					Tolerance::Absolute(epsilon) => diff < epsilon as $float,
+					Tolerance::Relative(epsilon) => {
+						diff <= epsilon as $float * self.abs().max(other.abs())
+					}
+					Tolerance::Ulps(ulps) => {
+						if self.is_nan() || other.is_nan() {
+							return false;
+						}
+						// map the bit patterns onto a monotonic integer scale, so that
+						// the distance between them counts the representable values in between
+						let ordered = |x: $float| {
+							let bits = x.to_bits() as $int;
+							if bits < 0 {
+								<$int>::MIN.wrapping_sub(bits)
+							} else {
+								bits
+							}
+						};
+						(ordered(self) as i128 - ordered(other) as i128).unsigned_abs()
+							<= ulps as u128
+					}
+				}
+			}
+		}
+	};
+}
+
+impl_approx_eq_float!(f64, i64);
+impl_approx_eq_float!(f32, i32);
+
+/// Adaption of assert_eq from the stdlib to work with `ApproxEq::approx_eq` rather than `std::cmp::PartialEq::eq`.
+/// An explicit `Tolerance` can be passed as `tolerance = ...` after the operands.
 #[macro_export]
 macro_rules! assert_approx_eq {
     ($left:expr, $right:expr) => ({
+        $crate::assert_approx_eq!($left, $right, tolerance = $crate::utils::Tolerance::default())
+    });
+    ($left:expr, $right:expr, tolerance = $tolerance:expr) => ({
         use $crate::utils::ApproxEq;
-        match (&$left, &$right) {
-            (left_val, right_val) => {
-                if !((left_val).approx_eq(*right_val)) {
+        match (&$left, &$right, $tolerance) {
+            (left_val, right_val, tolerance) => {
+                if !((left_val).approx_eq_with(*right_val, tolerance)) {
                     // The reborrows below are intentional. Without them, the stack slot for the
                     // borrow is initialized even before the values are compared, leading to a
                     // noticeable slow down.
                     panic!(r#"assertion failed: `(left ≈ right)`
   left: `{:?}`,
- right: `{:?}`"#, &*left_val, &*right_val)
+ right: `{:?}`,
+ tolerance: `{:?}`"#, &*left_val, &*right_val, tolerance)
                 }
             }
         }
     });
-    ($left:expr, $right:expr, $($arg:tt)+) => ({
+    ($left:expr, $right:expr, tolerance = $tolerance:expr, $($arg:tt)+) => ({
         use $crate::utils::ApproxEq;
-        match (&($left), &($right)) {
-            (left_val, right_val) => {
-                if !((left_val).approx_eq(*right_val)) {
+        match (&$left, &$right, $tolerance) {
+            (left_val, right_val, tolerance) => {
+                if !((left_val).approx_eq_with(*right_val, tolerance)) {
                     // The reborrows below are intentional. Without them, the stack slot for the
                     // borrow is initialized even before the values are compared, leading to a
                     // noticeable slow down.
                     panic!(r#"assertion failed: `(left ≈ right)`
   left: `{:?}`,
- right: `{:?}`: {}"#, &*left_val, &*right_val,
+ right: `{:?}`,
+ tolerance: `{:?}`: {}"#, &*left_val, &*right_val, tolerance,
                            format_args!($($arg)+))
                 }
             }
         }
     });
+    ($left:expr, $right:expr, $($arg:tt)+) => ({
+        $crate::assert_approx_eq!($left, $right, tolerance = $crate::utils::Tolerance::default(), $($arg)+)
+    });
 }
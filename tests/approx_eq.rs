@@ -0,0 +1,81 @@
+use raytrace::{
+	assert_approx_eq,
+	color::Color,
+	matrix::{Matrix, Matrix4x4},
+	tuple::Tuple,
+	utils::{ApproxEq, Tolerance},
+};
+
+#[test]
+fn absolute_tolerance() {
+	assert!(1.0f64.approx_eq(1.000001));
+	assert!(!1.0f64.approx_eq(1.0001));
+	assert!(1.0f32.approx_eq(1.000001));
+	assert!(1.0f64.approx_eq_with(1.05, Tolerance::Absolute(0.1)));
+	// The bound itself is not within the tolerance
+	assert!(!1.0f64.approx_eq_with(1.5, Tolerance::Absolute(0.5)));
+	assert!(1.0f64.approx_eq_with(1., Tolerance::Absolute(0.)));
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_helpers_match_approx_eq() {
+	use raytrace::utils::{EPSILON, EPSILON_F32, EPSILON_F64};
+
+	assert_eq!(EPSILON_F64, EPSILON);
+	assert_eq!(EPSILON_F32, EPSILON as f32);
+	assert!(raytrace::approx_eq(1.0f64, 1.000001));
+	assert!(!raytrace::approx_eq(1.0f32, 1.0001));
+}
+
+#[test]
+fn relative_tolerance() {
+	assert!(1e9f64.approx_eq_with(1e9 + 1., Tolerance::Relative(1e-6)));
+	assert!(!1e9f64.approx_eq(1e9 + 1.));
+	assert!(!1e-9f64.approx_eq_with(2e-9, Tolerance::Relative(1e-6)));
+}
+
+#[test]
+fn ulps_tolerance() {
+	let a = 0.1f64 + 0.2;
+	assert!(!a.approx_eq_with(0.3, Tolerance::Ulps(0)));
+	assert!(a.approx_eq_with(0.3, Tolerance::Ulps(1)));
+	assert!(f32::MIN_POSITIVE.approx_eq_with(-f32::MIN_POSITIVE, Tolerance::Ulps(1 << 24)));
+	assert!(!f64::NAN.approx_eq_with(f64::NAN, Tolerance::Ulps(u64::MAX)));
+	assert!(f64::INFINITY.approx_eq_with(f64::INFINITY, Tolerance::Ulps(0)));
+}
+
+#[test]
+fn composite_types() {
+	assert!(Tuple::point(1., 2., 3.).approx_eq(Tuple::point(1.000001, 2., 3.)));
+	assert!(!Tuple::point(1., 2., 3.).approx_eq(Tuple::vector(1., 2., 3.)));
+	assert!(Color::new(0.5, 0.5, 0.5).approx_eq(Color::new(0.5, 0.500001, 0.5)));
+
+	let a: Matrix4x4<f64> = Matrix::identity();
+	let b = a * 1.000001;
+	assert_approx_eq!(&a, &b);
+	assert_approx_eq!(&a, &(a * 1.01), tolerance = Tolerance::Relative(0.02));
+}
+
+#[test]
+fn partial_eq_is_exact() {
+	let a = Tuple::vector(0.1 + 0.2, 0., 0.);
+	let b = Tuple::vector(0.3, 0., 0.);
+
+	assert_ne!(a, b);
+	assert_approx_eq!(a, b);
+	assert_approx_eq!(
+		a,
+		b,
+		tolerance = Tolerance::Ulps(1),
+		"{:?} and {:?} differ",
+		a,
+		b
+	);
+}
+
+#[test]
+#[should_panic]
+fn assert_approx_eq_with_tight_tolerance() {
+	assert_approx_eq!(1.0f64, 1.001, tolerance = Tolerance::Absolute(1e-4));
+}
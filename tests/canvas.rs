@@ -1,5 +1,7 @@
-use raytrace::canvas::Canvas;
+use raytrace::canvas::{BitDepth, Canvas};
 use raytrace::color::*;
+use raytrace::error::Error;
+use raytrace::{assert_approx_eq, utils::Tolerance};
 
 #[test]
 fn read_and_write() {
@@ -58,3 +60,183 @@ fn end_in_newline() {
 	let c = Canvas::new(5, 3);
 	assert_eq!(c.as_ppm().chars().last(), Some('\n'));
 }
+
+#[test]
+fn write_pixel_out_of_bounds() {
+	let mut c = Canvas::new(3, 2);
+	assert_eq!(c.write_pixel(2, 1, RED), Ok(()));
+	assert_eq!(c[(1, 2)], RED);
+
+	let err = c.write_pixel(1, 2, RED).unwrap_err();
+	assert_eq!(
+		err,
+		Error::OutOfBounds {
+			x: 1,
+			y: 2,
+			width: 3,
+			height: 2
+		}
+	);
+	assert_eq!(
+		err.to_string(),
+		"index (1, 2) is out of bounds for a 3x2 grid"
+	);
+}
+
+#[test]
+fn ppm_16_bit() {
+	let mut c = Canvas::new(2, 1);
+	c[(0, 0)] = Color::new(1.5, 0.5, -1.);
+	c[(0, 1)] = Color::new(0.25, 1., 0.);
+
+	assert_eq!(
+		c.as_p3(BitDepth::Sixteen),
+		"P3\n2 1\n65535\n65535 32768 0 16384 65535 0\n"
+	);
+}
+
+#[test]
+fn binary_ppm() {
+	let mut c = Canvas::new(2, 1);
+	c[(0, 0)] = Color::new(1.5, 0.5, -1.);
+	c[(0, 1)] = Color::new(0.25, 1., 0.);
+
+	let mut expected = b"P6\n2 1\n255\n".to_vec();
+	expected.extend_from_slice(&[255, 128, 0, 64, 255, 0]);
+	assert_eq!(c.as_p6(BitDepth::Eight), expected);
+
+	let mut expected = b"P6\n2 1\n65535\n".to_vec();
+	expected.extend_from_slice(&[255, 255, 128, 0, 0, 0, 64, 0, 255, 255, 0, 0]);
+	assert_eq!(c.as_p6(BitDepth::Sixteen), expected);
+}
+
+#[test]
+fn streaming_matches_in_memory() {
+	let mut c = Canvas::new(10, 3);
+	for (i, pixel) in c.pixels.iter_mut().enumerate() {
+		*pixel = Color::new(i as f64 / 30., 0.8, 1. - i as f64 / 30.);
+	}
+
+	let mut p3 = Vec::new();
+	c.write_ppm(&mut p3).unwrap();
+	assert_eq!(String::from_utf8(p3).unwrap(), c.as_ppm());
+	assert!(c.as_ppm().lines().all(|line| line.len() <= 70));
+
+	let mut p6 = Vec::new();
+	c.write_p6(&mut p6, BitDepth::Sixteen).unwrap();
+	assert_eq!(p6, c.as_p6(BitDepth::Sixteen));
+}
+
+#[test]
+fn save_picks_encoder_from_extension() {
+	let mut c = Canvas::new(3, 2);
+	c[(1, 2)] = RED;
+	let path = std::env::temp_dir().join(format!("raytrace-save-{}.PPM", std::process::id()));
+
+	c.save(&path).unwrap();
+	let written = std::fs::read(&path).unwrap();
+	std::fs::remove_file(&path).unwrap();
+	assert_eq!(written, c.as_ppm().into_bytes());
+
+	assert_eq!(
+		c.save(std::env::temp_dir().join("raytrace.xyz")),
+		Err(Error::UnsupportedFormat("xyz".into()))
+	);
+}
+
+#[test]
+fn read_plain_ppm_with_comments() {
+	let data = b"P3\n# created by hand\n2 1 # width height\n  4\n4 2 0\n# middle\n0 1 4\n";
+	let c = Canvas::from_ppm(data).unwrap();
+
+	assert_eq!((c.width, c.height), (2, 1));
+	assert_eq!(c[(0, 0)], Color::new(1., 0.5, 0.));
+	assert_eq!(c[(0, 1)], Color::new(0., 0.25, 1.));
+}
+
+#[test]
+fn read_binary_ppm_round_trips() {
+	let mut c = Canvas::new(3, 2);
+	c[(0, 0)] = Color::new(1., 0.2, 0.4);
+	c[(1, 2)] = Color::new(0.6, 0.8, 0.);
+
+	for &depth in &[BitDepth::Eight, BitDepth::Sixteen] {
+		let read = Canvas::read_ppm(&c.as_p6(depth)[..]).unwrap();
+		let tolerance = 0.5 / depth.max_value() as f64;
+		assert_eq!((read.width, read.height), (3, 2));
+		for (actual, expected) in read.iter().zip(c.iter()) {
+			assert_approx_eq!(
+				*actual,
+				*expected,
+				tolerance = Tolerance::Absolute(tolerance)
+			);
+		}
+	}
+
+	let read = Canvas::from_ppm(c.as_ppm().as_bytes()).unwrap();
+	assert_eq!(read.as_ppm(), c.as_ppm());
+}
+
+#[test]
+fn read_grayscale_pgm() {
+	let plain = Canvas::from_ppm(b"P2 2 1 10 10 5").unwrap();
+	assert_eq!(plain.pixels, vec![WHITE, Color::new(0.5, 0.5, 0.5)]);
+
+	let raw = Canvas::from_ppm(b"P5\n2 1\n1000\n\x03\xe8\x00\x00").unwrap();
+	assert_eq!(raw.pixels, vec![WHITE, BLACK]);
+}
+
+#[test]
+fn read_rejects_malformed_images() {
+	let malformed: &[&[u8]] = &[
+		b"P7\n1 1\n255\n",
+		b"P3\n1 1\n0\n0 0 0",
+		b"P3\n1 1\n255\n0 0",
+		b"P3\n1 1\n255\n0 x 0",
+		b"P3\n1 1\n255\n0 256 0",
+		b"P6\n2 1\n255\n\x00\x00\x00",
+		b"P6\n1 1\n255",
+	];
+	for data in malformed {
+		assert!(matches!(
+			Canvas::from_ppm(data),
+			Err(Error::InvalidImage(_))
+		));
+	}
+}
+
+#[test]
+fn load_reads_saved_canvas() {
+	let mut c = Canvas::new(2, 2);
+	c[(1, 0)] = WHITE;
+	let path = std::env::temp_dir().join(format!("raytrace-load-{}.ppm", std::process::id()));
+
+	c.save(&path).unwrap();
+	let loaded = Canvas::load(&path);
+	std::fs::remove_file(&path).unwrap();
+	assert_eq!(loaded.unwrap().pixels, c.pixels);
+
+	assert!(matches!(
+		Canvas::load(std::env::temp_dir().join("raytrace-missing.ppm")),
+		Err(Error::Io(_))
+	));
+}
+
+#[test]
+fn io_errors_keep_their_source() {
+	use std::error::Error as _;
+
+	let err = match Canvas::load(std::env::temp_dir().join("raytrace-missing.ppm")) {
+		Err(err) => err,
+		Ok(_) => panic!("loaded a missing file"),
+	};
+	let source = err
+		.source()
+		.and_then(|e| e.downcast_ref::<std::io::Error>())
+		.unwrap();
+
+	assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+	assert!(source.raw_os_error().is_some());
+	assert_eq!(err.clone(), err);
+	assert!(Error::NonInvertible.source().is_none());
+}
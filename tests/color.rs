@@ -1,4 +1,4 @@
-use raytrace::color::Color;
+use raytrace::{assert_approx_eq, color::Color};
 
 #[test]
 fn colors_are_rgb_tuples() {
@@ -17,7 +17,7 @@ fn adding_colors() {
 	let actual = c1 + c2;
 	let expected = Color::new(1.6, 0.7, 1.);
 
-	assert_eq!(expected, actual);
+	assert_approx_eq!(expected, actual);
 }
 
 #[test]
@@ -28,7 +28,7 @@ fn subtracting_colors() {
 	let actual = c1 - c2;
 	let expected = Color::new(0.2, 0.5, 0.5);
 
-	assert_eq!(expected, actual);
+	assert_approx_eq!(expected, actual);
 }
 
 #[test]
@@ -38,7 +38,7 @@ fn multiplying_color_by_scalar() {
 	let actual = c * 2.0;
 	let expected = Color::new(0.4, 0.6, 0.8);
 
-	assert_eq!(expected, actual);
+	assert_approx_eq!(expected, actual);
 }
 
 #[test]
@@ -49,5 +49,26 @@ fn multiplying_colors() {
 	let actual = c1 * c2;
 	let expected = Color::new(0.9, 0.2, 0.04);
 
-	assert_eq!(expected, actual);
+	assert_approx_eq!(expected, actual);
+}
+
+#[test]
+fn single_precision_colors() {
+	let c1: Color<f32> = Color::new(1., 0.2, 0.4);
+	let c2: Color<f32> = Color::new(0.9, 1., 0.1);
+
+	assert_approx_eq!(c1 * c2, Color::new(0.9, 0.2, 0.04));
+	assert_approx_eq!(c1 + c2, Color::new(1.9, 1.2, 0.5));
+	assert_approx_eq!(c1 * 2., Color::new(2., 0.4, 0.8));
+}
+
+#[test]
+fn parse_and_display_colors() {
+	let c: Color = "color(-0.5, 0.4, 1.7)".parse().unwrap();
+	assert_eq!(c, Color::new(-0.5, 0.4, 1.7));
+	assert_eq!(c.to_string(), "color(-0.5, 0.4, 1.7)");
+	assert_eq!(c.to_string().parse(), Ok(c));
+
+	assert!("color(1, 2)".parse::<Color>().is_err());
+	assert!("rgb(1, 2, 3)".parse::<Color>().is_err());
 }
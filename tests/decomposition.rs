@@ -0,0 +1,83 @@
+use raytrace::{
+	assert_approx_eq, matrix, matrix::Matrix, quaternion::Quaternion, transformations::*,
+	tuple::Tuple, utils::Tolerance,
+};
+use std::f64::consts::PI;
+
+#[test]
+fn decompose_trs() {
+	let m = translation(1., -2., 3.) * rotation_y(PI / 3.) * scaling(2., 3., 4.);
+	let d = m.decompose().unwrap();
+
+	assert_eq!(d.translation, Tuple::vector(1., -2., 3.));
+	assert_approx_eq!(&d.rotation.to_matrix(), &rotation_y(PI / 3.));
+	assert_eq!(d.scale, Tuple::vector(2., 3., 4.));
+	assert_approx_eq!(d.shear[0], 0.);
+	assert_approx_eq!(d.shear[1], 0.);
+	assert_approx_eq!(d.shear[2], 0.);
+}
+
+#[test]
+fn decompose_with_shear() {
+	let m = translation(5., 0., 0.)
+		* rotation_x(0.3)
+		* shearing(0.5, 0.25, 0., 2., 0., 0.)
+		* scaling(1., 2., 3.);
+	let d = m.decompose().unwrap();
+
+	assert_approx_eq!(d.shear[0], 0.5);
+	assert_approx_eq!(d.shear[1], 0.25);
+	assert_approx_eq!(d.shear[2], 2.);
+	assert_approx_eq!(d.scale, Tuple::vector(1., 2., 3.));
+	assert_approx_eq!(
+		d.rotation,
+		Quaternion::from_axis_angle(Tuple::vector(1., 0., 0.), 0.3)
+	);
+}
+
+#[test]
+fn decompose_round_trip() {
+	let matrices = [
+		Matrix::identity(),
+		scaling(-1., 1., 1.),
+		translation(10., 5., 7.) * scaling(5., 5., 5.) * rotation_x(PI / 2.),
+		shearing(1., 0.5, 0.2, 0.3, 0.7, 0.1) * rotation_z(1.) * translation(1., 2., 3.),
+		matrix![ 4, 4 =>
+			 2., 0.5, -1., 4.;
+			 0., 3.,   1., 5.;
+			-1., 0.,   2., 6.;
+			 0., 0.,   0., 1.;
+		],
+	];
+
+	for m in matrices.iter() {
+		assert_approx_eq!(&m.decompose().unwrap().to_matrix(), m);
+	}
+}
+
+#[test]
+fn decompose_tiny_scale() {
+	let m = translation(1e-6, 0., -2e-6) * rotation_z(0.7) * scaling(1e-6, 2e-6, 3e-6);
+	let d = m.decompose().unwrap();
+
+	assert_approx_eq!(
+		d.scale,
+		Tuple::vector(1e-6, 2e-6, 3e-6),
+		tolerance = Tolerance::Relative(1e-9)
+	);
+	assert_approx_eq!(&d.rotation.to_matrix(), &rotation_z(0.7));
+	assert_approx_eq!(&d.to_matrix(), &m, tolerance = Tolerance::Absolute(1e-15));
+}
+
+#[test]
+fn cannot_decompose_projective_or_singular() {
+	let projective = matrix![ 4, 4 =>
+		1., 0., 0., 0.;
+		0., 1., 0., 0.;
+		0., 0., 1., 0.;
+		0., 0., 1., 0.;
+	];
+
+	assert!(projective.decompose().is_none());
+	assert!(scaling(1., 0., 1.).decompose().is_none());
+}
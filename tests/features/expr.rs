@@ -0,0 +1,519 @@
+//! Evaluator for the expressions appearing in steps, e.g. `a * 3.5`,
+//! `normalize(vector(1, 2, 3))`, `B[3,2]`, `√2/2` or `rotation_x(π / 4)`.
+
+use std::{collections::HashMap, f64::consts::PI, fmt};
+
+use raytrace::{
+	canvas::Canvas,
+	color::Color,
+	matrix::{Determinant, Matrix2x2, Matrix3x3, Matrix4x4},
+	transformations,
+	tuple::Tuple,
+	utils::ApproxEq,
+};
+
+/// Why a step stopped the scenario
+pub enum Halt {
+	/// The step uses something that has no definition yet
+	Pending(String),
+	/// The step ran and its expectation did not hold
+	Failed(String),
+}
+
+pub fn pending<T>(msg: impl Into<String>) -> Result<T, Halt> {
+	Err(Halt::Pending(msg.into()))
+}
+
+pub fn failed<T>(msg: impl Into<String>) -> Result<T, Halt> {
+	Err(Halt::Failed(msg.into()))
+}
+
+#[derive(Clone)]
+pub enum Value {
+	Number(f64),
+	Tuple(Tuple),
+	Color(Color),
+	Canvas(Canvas),
+	Text(String),
+	Matrix2(Matrix2x2<f64>),
+	Matrix3(Matrix3x3<f64>),
+	Matrix4(Matrix4x4<f64>),
+}
+
+impl Value {
+	fn kind(&self) -> &'static str {
+		match self {
+			Value::Number(_) => "number",
+			Value::Tuple(_) => "tuple",
+			Value::Color(_) => "color",
+			Value::Canvas(_) => "canvas",
+			Value::Text(_) => "text",
+			Value::Matrix2(_) => "2x2 matrix",
+			Value::Matrix3(_) => "3x3 matrix",
+			Value::Matrix4(_) => "4x4 matrix",
+		}
+	}
+
+	pub fn number(&self) -> Result<f64, Halt> {
+		match self {
+			Value::Number(n) => Ok(*n),
+			v => failed(format!("expected a number, found a {}", v.kind())),
+		}
+	}
+
+	pub fn index(&self) -> Result<usize, Halt> {
+		let n = self.number()?;
+		if n >= 0. && n.fract() == 0. {
+			Ok(n as usize)
+		} else {
+			failed(format!("expected an index, found {}", n))
+		}
+	}
+
+	pub fn tuple(&self) -> Result<Tuple, Halt> {
+		match self {
+			Value::Tuple(t) => Ok(*t),
+			v => failed(format!("expected a tuple, found a {}", v.kind())),
+		}
+	}
+
+	pub fn color(&self) -> Result<Color, Halt> {
+		match self {
+			Value::Color(c) => Ok(*c),
+			v => failed(format!("expected a color, found a {}", v.kind())),
+		}
+	}
+
+	pub fn text(&self) -> Result<&str, Halt> {
+		match self {
+			Value::Text(s) => Ok(s),
+			v => failed(format!("expected text, found a {}", v.kind())),
+		}
+	}
+
+	/// Equality as used by the specification, i.e. floats within `EPSILON`
+	pub fn approx_eq(&self, other: &Value) -> Result<bool, Halt> {
+		Ok(match (self, other) {
+			(Value::Number(a), Value::Number(b)) => a.approx_eq(*b),
+			(Value::Tuple(a), Value::Tuple(b)) => a.approx_eq(*b),
+			(Value::Color(a), Value::Color(b)) => a.approx_eq(*b),
+			(Value::Text(a), Value::Text(b)) => a == b,
+			(Value::Matrix2(a), Value::Matrix2(b)) => a.approx_eq(b),
+			(Value::Matrix3(a), Value::Matrix3(b)) => a.approx_eq(b),
+			(Value::Matrix4(a), Value::Matrix4(b)) => a.approx_eq(b),
+			(a, b) => return failed(format!("cannot compare a {} with a {}", a.kind(), b.kind())),
+		})
+	}
+
+	/// Parse a step's data table into a matrix of the table's size
+	pub fn matrix_from_table(table: &[String]) -> Result<Value, Halt> {
+		let src = table.join("\n");
+		let parsed = match (
+			table.len(),
+			table.first().map(|row| row.matches('|').count() - 1),
+		) {
+			(2, Some(2)) => src.parse().map(Value::Matrix2),
+			(3, Some(3)) => src.parse().map(Value::Matrix3),
+			(4, Some(4)) => src.parse().map(Value::Matrix4),
+			(rows, cols) => return pending(format!("no {}x{} matrices", rows, cols.unwrap_or(0))),
+		};
+		parsed.or_else(|e| failed(e.to_string()))
+	}
+}
+
+impl fmt::Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Value::Number(n) => write!(f, "{}", n),
+			Value::Tuple(t) => write!(f, "{}", t),
+			Value::Color(c) => write!(f, "{}", c),
+			Value::Canvas(c) => write!(f, "canvas({}, {})", c.width, c.height),
+			Value::Text(s) => write!(f, "{:?}", s),
+			Value::Matrix2(m) => write!(f, "\n{}", m),
+			Value::Matrix3(m) => write!(f, "\n{}", m),
+			Value::Matrix4(m) => write!(f, "\n{}", m),
+		}
+	}
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+	Number(f64),
+	Ident(String),
+	Symbol(char),
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, Halt> {
+	let mut tokens = Vec::new();
+	let mut chars = src.chars().peekable();
+	while let Some(&c) = chars.peek() {
+		if c.is_whitespace() {
+			chars.next();
+		} else if c.is_ascii_digit() {
+			let mut s = String::new();
+			while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit() || **c == '.') {
+				s.push(c);
+				chars.next();
+			}
+			match s.parse() {
+				Ok(n) => tokens.push(Token::Number(n)),
+				Err(_) => return pending(format!("cannot read number `{}`", s)),
+			}
+		} else if c == 'π' {
+			tokens.push(Token::Number(PI));
+			chars.next();
+		} else if c.is_alphabetic() || c == '_' {
+			let mut s = String::new();
+			while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+				s.push(c);
+				chars.next();
+			}
+			tokens.push(Token::Ident(s));
+		} else if "+-*/()[],.√".contains(c) {
+			tokens.push(Token::Symbol(c));
+			chars.next();
+		} else {
+			return pending(format!("cannot read `{}`", c));
+		}
+	}
+	Ok(tokens)
+}
+
+const FUNCTIONS: &[&str] = &[
+	"tuple",
+	"point",
+	"vector",
+	"color",
+	"canvas",
+	"magnitude",
+	"normalize",
+	"dot",
+	"cross",
+	"reflect",
+	"transpose",
+	"determinant",
+	"submatrix",
+	"minor",
+	"cofactor",
+	"inverse",
+	"translation",
+	"scaling",
+	"rotation_x",
+	"rotation_y",
+	"rotation_z",
+	"shearing",
+	"view_transform",
+	"pixel_at",
+	"canvas_to_ppm",
+];
+
+pub fn eval(src: &str, vars: &HashMap<String, Value>) -> Result<Value, Halt> {
+	let mut parser = Parser {
+		tokens: tokenize(src)?,
+		pos: 0,
+		vars,
+	};
+	let value = parser.expr()?;
+	if parser.pos < parser.tokens.len() {
+		return pending(format!("cannot read `{}`", src));
+	}
+	Ok(value)
+}
+
+/// Recursive descent evaluation of
+///
+/// ```text
+/// expr    := term (('+' | '-') term)*
+/// term    := unary (('*' | '/') unary)*
+/// unary   := ('-' | '√') unary | postfix
+/// postfix := primary ('.' ident | '[' args ']')*
+/// primary := number | ident | ident '(' args ')' | '(' expr ')'
+/// ```
+struct Parser<'a> {
+	tokens: Vec<Token>,
+	pos: usize,
+	vars: &'a HashMap<String, Value>,
+}
+
+impl Parser<'_> {
+	fn next(&mut self) -> Option<Token> {
+		let token = self.tokens.get(self.pos).cloned();
+		self.pos += 1;
+		token
+	}
+
+	fn eat(&mut self, symbol: char) -> bool {
+		if self.tokens.get(self.pos) == Some(&Token::Symbol(symbol)) {
+			self.pos += 1;
+			true
+		} else {
+			false
+		}
+	}
+
+	fn expect(&mut self, symbol: char) -> Result<(), Halt> {
+		if self.eat(symbol) {
+			Ok(())
+		} else {
+			pending(format!("expected `{}`", symbol))
+		}
+	}
+
+	fn expr(&mut self) -> Result<Value, Halt> {
+		let mut value = self.term()?;
+		loop {
+			if self.eat('+') {
+				value = binary('+', value, self.term()?)?;
+			} else if self.eat('-') {
+				value = binary('-', value, self.term()?)?;
+			} else {
+				return Ok(value);
+			}
+		}
+	}
+
+	fn term(&mut self) -> Result<Value, Halt> {
+		let mut value = self.unary()?;
+		loop {
+			if self.eat('*') {
+				value = binary('*', value, self.unary()?)?;
+			} else if self.eat('/') {
+				value = binary('/', value, self.unary()?)?;
+			} else {
+				return Ok(value);
+			}
+		}
+	}
+
+	fn unary(&mut self) -> Result<Value, Halt> {
+		if self.eat('-') {
+			match self.unary()? {
+				Value::Number(n) => Ok(Value::Number(-n)),
+				Value::Tuple(t) => Ok(Value::Tuple(-t)),
+				v => failed(format!("cannot negate a {}", v.kind())),
+			}
+		} else if self.eat('√') {
+			Ok(Value::Number(self.unary()?.number()?.sqrt()))
+		} else {
+			self.postfix()
+		}
+	}
+
+	fn postfix(&mut self) -> Result<Value, Halt> {
+		let mut value = self.primary()?;
+		loop {
+			if self.eat('.') {
+				let field = match self.next() {
+					Some(Token::Ident(field)) => field,
+					_ => return pending("expected a field name"),
+				};
+				value = Value::Number(match (&value, field.as_str()) {
+					(Value::Tuple(t), "x") => t.x,
+					(Value::Tuple(t), "y") => t.y,
+					(Value::Tuple(t), "z") => t.z,
+					(Value::Tuple(t), "w") => t.w,
+					(Value::Color(c), "red") => c.r,
+					(Value::Color(c), "green") => c.g,
+					(Value::Color(c), "blue") => c.b,
+					(Value::Canvas(c), "width") => c.width as f64,
+					(Value::Canvas(c), "height") => c.height as f64,
+					(v, field) => {
+						return pending(format!("no field `{}` on a {}", field, v.kind()))
+					}
+				});
+			} else if self.eat('[') {
+				let args = self.args(']')?;
+				value = match (&value, args.as_slice()) {
+					(Value::Matrix2(m), [i, j]) => Value::Number(m[coords(i, j, 2, 2)?]),
+					(Value::Matrix3(m), [i, j]) => Value::Number(m[coords(i, j, 3, 3)?]),
+					(Value::Matrix4(m), [i, j]) => Value::Number(m[coords(i, j, 4, 4)?]),
+					(v, _) => return pending(format!("cannot index a {}", v.kind())),
+				};
+			} else {
+				return Ok(value);
+			}
+		}
+	}
+
+	fn primary(&mut self) -> Result<Value, Halt> {
+		match self.next() {
+			Some(Token::Number(n)) => Ok(Value::Number(n)),
+			Some(Token::Symbol('(')) => {
+				let value = self.expr()?;
+				self.expect(')')?;
+				Ok(value)
+			}
+			Some(Token::Ident(name)) if self.eat('(') => {
+				if !FUNCTIONS.contains(&name.as_str()) {
+					return pending(format!("no function `{}`", name));
+				}
+				let args = self.args(')')?;
+				call(&name, &args)
+			}
+			Some(Token::Ident(name)) => match self.vars.get(&name) {
+				Some(value) => Ok(value.clone()),
+				None if name == "identity_matrix" => Ok(Value::Matrix4(Matrix4x4::identity())),
+				None => pending(format!("no variable `{}`", name)),
+			},
+			_ => pending("expected a value"),
+		}
+	}
+
+	/// Comma separated values up to the closing `close`
+	fn args(&mut self, close: char) -> Result<Vec<Value>, Halt> {
+		let mut args = Vec::new();
+		if self.eat(close) {
+			return Ok(args);
+		}
+		loop {
+			args.push(self.expr()?);
+			if self.eat(close) {
+				return Ok(args);
+			}
+			self.expect(',')?;
+		}
+	}
+}
+
+fn binary(op: char, lhs: Value, rhs: Value) -> Result<Value, Halt> {
+	use Value::*;
+
+	Ok(match (op, lhs, rhs) {
+		('+', Number(a), Number(b)) => Number(a + b),
+		('-', Number(a), Number(b)) => Number(a - b),
+		('*', Number(a), Number(b)) => Number(a * b),
+		('/', Number(a), Number(b)) => Number(a / b),
+		('+', Tuple(a), Tuple(b)) => Tuple(a + b),
+		('-', Tuple(a), Tuple(b)) => Tuple(a - b),
+		('*', Tuple(a), Number(b)) => Tuple(a * b),
+		('/', Tuple(a), Number(b)) => Tuple(a / b),
+		('+', Color(a), Color(b)) => Color(a + b),
+		('-', Color(a), Color(b)) => Color(a - b),
+		('*', Color(a), Number(b)) => Color(a * b),
+		('*', Color(a), Color(b)) => Color(a * b),
+		('*', Matrix2(a), Matrix2(b)) => Matrix2(a * b),
+		('*', Matrix3(a), Matrix3(b)) => Matrix3(a * b),
+		('*', Matrix4(a), Matrix4(b)) => Matrix4(a * b),
+		('*', Matrix4(a), Tuple(b)) => Tuple(a * b),
+		(op, a, b) => return failed(format!("cannot evaluate {} {} {}", a.kind(), op, b.kind())),
+	})
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value, Halt> {
+	use Value::*;
+
+	Ok(match (name, args) {
+		("tuple", [x, y, z, w]) => Tuple(raytrace::tuple::Tuple::new(
+			x.number()?,
+			y.number()?,
+			z.number()?,
+			w.number()?,
+		)),
+		("point", [x, y, z]) => Tuple(raytrace::tuple::Tuple::point(
+			x.number()?,
+			y.number()?,
+			z.number()?,
+		)),
+		("vector", [x, y, z]) => Tuple(raytrace::tuple::Tuple::vector(
+			x.number()?,
+			y.number()?,
+			z.number()?,
+		)),
+		("color", [r, g, b]) => Color(raytrace::color::Color::new(
+			r.number()?,
+			g.number()?,
+			b.number()?,
+		)),
+		("canvas", [width, height]) => Canvas(raytrace::canvas::Canvas::new(
+			width.index()?,
+			height.index()?,
+		)),
+		("magnitude", [v]) => Number(v.tuple()?.magnitude()),
+		("normalize", [v]) => Tuple(v.tuple()?.normalize()),
+		("dot", [a, b]) => Number(a.tuple()?.dot(&b.tuple()?)),
+		("cross", [a, b]) => Tuple(a.tuple()?.cross(&b.tuple()?)),
+		("reflect", [v, n]) => Tuple(v.tuple()?.reflect(n.tuple()?)),
+		("transpose", [Matrix2(m)]) => Matrix2(m.transpose()),
+		("transpose", [Matrix3(m)]) => Matrix3(m.transpose()),
+		("transpose", [Matrix4(m)]) => Matrix4(m.transpose()),
+		("determinant", [Matrix2(m)]) => Number(m.det()),
+		("determinant", [Matrix3(m)]) => Number(m.det()),
+		("determinant", [Matrix4(m)]) => Number(m.det()),
+		("submatrix", [Matrix3(m), i, j]) => {
+			let (i, j) = coords(i, j, 3, 3)?;
+			Matrix2(m.submatrix::<2, 2>(i, j))
+		}
+		("submatrix", [Matrix4(m), i, j]) => {
+			let (i, j) = coords(i, j, 4, 4)?;
+			Matrix3(m.submatrix::<3, 3>(i, j))
+		}
+		("minor", [Matrix3(m), i, j]) => {
+			let (i, j) = coords(i, j, 3, 3)?;
+			Number(m.minor(i, j))
+		}
+		("minor", [Matrix4(m), i, j]) => {
+			let (i, j) = coords(i, j, 4, 4)?;
+			Number(m.minor(i, j))
+		}
+		("cofactor", [Matrix3(m), i, j]) => {
+			let (i, j) = coords(i, j, 3, 3)?;
+			Number(m.cofactor(i, j))
+		}
+		("cofactor", [Matrix4(m), i, j]) => {
+			let (i, j) = coords(i, j, 4, 4)?;
+			Number(m.cofactor(i, j))
+		}
+		("inverse", [Matrix2(m)]) => Matrix2(invert(m.invert())?),
+		("inverse", [Matrix3(m)]) => Matrix3(invert(m.invert())?),
+		("inverse", [Matrix4(m)]) => Matrix4(invert(m.invert())?),
+		("translation", [x, y, z]) => Matrix4(transformations::translation(
+			x.number()?,
+			y.number()?,
+			z.number()?,
+		)),
+		("scaling", [x, y, z]) => Matrix4(transformations::scaling(
+			x.number()?,
+			y.number()?,
+			z.number()?,
+		)),
+		("rotation_x", [r]) => Matrix4(transformations::rotation_x(r.number()?)),
+		("rotation_y", [r]) => Matrix4(transformations::rotation_y(r.number()?)),
+		("rotation_z", [r]) => Matrix4(transformations::rotation_z(r.number()?)),
+		("shearing", [xy, xz, yx, yz, zx, zy]) => Matrix4(transformations::shearing(
+			xy.number()?,
+			xz.number()?,
+			yx.number()?,
+			yz.number()?,
+			zx.number()?,
+			zy.number()?,
+		)),
+		("view_transform", [from, to, up]) => Matrix4(transformations::view_transform(
+			from.tuple()?,
+			to.tuple()?,
+			up.tuple()?,
+		)),
+		("pixel_at", [Canvas(c), x, y]) => Color(c[coords(y, x, c.height, c.width)?]),
+		("canvas_to_ppm", [Canvas(c)]) => Text(c.as_ppm()),
+		(name, args) => {
+			let kinds: Vec<_> = args.iter().map(Value::kind).collect();
+			return failed(format!("{} cannot take ({})", name, kinds.join(", ")));
+		}
+	})
+}
+
+/// Row and column as indices, failing unless they lie within `rows` x `cols`
+fn coords(i: &Value, j: &Value, rows: usize, cols: usize) -> Result<(usize, usize), Halt> {
+	let (i, j) = (i.index()?, j.index()?);
+	if i < rows && j < cols {
+		Ok((i, j))
+	} else {
+		failed(format!(
+			"({}, {}) is outside {} rows and {} columns",
+			i, j, rows, cols
+		))
+	}
+}
+
+fn invert<M>(inverse: Option<M>) -> Result<M, Halt> {
+	inverse.map_or_else(|| failed("matrix is not invertible"), Ok)
+}
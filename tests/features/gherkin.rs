@@ -0,0 +1,200 @@
+//! Parser for the subset of Gherkin used by the files in `features/`:
+//! `Feature`, `Background`, `Scenario`, `Scenario Outline` with `Examples`,
+//! data tables, doc strings and comments.
+
+use std::{fs, io, path::Path};
+
+pub struct Feature {
+	pub name: String,
+	pub scenarios: Vec<Scenario>,
+}
+
+pub struct Scenario {
+	pub name: String,
+	pub steps: Vec<Step>,
+}
+
+#[derive(Clone)]
+pub struct Step {
+	pub keyword: String,
+	pub text: String,
+	/// Raw `| a | b |` lines of an attached data table
+	pub table: Vec<String>,
+	pub docstring: Option<String>,
+}
+
+impl Step {
+	/// Replace every `<name>` placeholder with the value from an examples row
+	fn substitute(&self, header: &[String], row: &[String]) -> Step {
+		let replace = |s: &str| {
+			header
+				.iter()
+				.zip(row)
+				.fold(s.to_string(), |s, (name, value)| {
+					s.replace(&format!("<{}>", name), value)
+				})
+		};
+		Step {
+			keyword: self.keyword.clone(),
+			text: replace(&self.text),
+			table: self.table.iter().map(|line| replace(line)).collect(),
+			docstring: self.docstring.as_deref().map(replace),
+		}
+	}
+}
+
+/// Split a `| a | b |` table line into its trimmed cells
+pub fn cells(line: &str) -> Vec<String> {
+	line.trim()
+		.trim_start_matches('|')
+		.trim_end_matches('|')
+		.split('|')
+		.map(|cell| cell.trim().to_string())
+		.collect()
+}
+
+#[derive(Default)]
+struct Block {
+	name: String,
+	steps: Vec<Step>,
+	outline: bool,
+	/// Header and rows of each `Examples:` table
+	examples: Vec<(Vec<String>, Vec<Vec<String>>)>,
+}
+
+/// Steps of the block currently being read; steps before any scenario count as background
+fn current_steps<'a>(
+	in_background: bool,
+	background: &'a mut Vec<Step>,
+	blocks: &'a mut [Block],
+) -> &'a mut Vec<Step> {
+	match blocks.last_mut() {
+		Some(block) if !in_background => &mut block.steps,
+		_ => background,
+	}
+}
+
+pub fn parse_file(path: &Path) -> io::Result<Feature> {
+	fs::read_to_string(path).map(|src| parse(&src))
+}
+
+pub fn parse(src: &str) -> Feature {
+	let mut name = String::new();
+	let mut background = Vec::new();
+	let mut blocks: Vec<Block> = Vec::new();
+	let mut in_background = false;
+	let mut in_examples = false;
+	// Indentation of the opening `"""` and the lines collected so far
+	let mut docstring: Option<(usize, Vec<String>)> = None;
+
+	for line in src.lines() {
+		let trimmed = line.trim();
+
+		if let Some((indent, mut lines)) = docstring.take() {
+			if trimmed == "\"\"\"" {
+				if let Some(step) =
+					current_steps(in_background, &mut background, &mut blocks).last_mut()
+				{
+					step.docstring = Some(lines.join("\n"));
+				}
+			} else {
+				let strip = line
+					.char_indices()
+					.take_while(|&(i, c)| i < indent && c.is_whitespace())
+					.count();
+				lines.push(line[strip..].to_string());
+				docstring = Some((indent, lines));
+			}
+			continue;
+		}
+
+		if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('@') {
+			continue;
+		} else if trimmed.starts_with("\"\"\"") {
+			docstring = Some((line.len() - line.trim_start().len(), Vec::new()));
+		} else if trimmed.starts_with('|') {
+			if in_examples {
+				let (header, rows) = blocks.last_mut().unwrap().examples.last_mut().unwrap();
+				if header.is_empty() {
+					*header = cells(trimmed);
+				} else {
+					rows.push(cells(trimmed));
+				}
+			} else if let Some(step) =
+				current_steps(in_background, &mut background, &mut blocks).last_mut()
+			{
+				step.table.push(trimmed.to_string());
+			}
+		} else if let Some(rest) = trimmed.strip_prefix("Feature:") {
+			name = rest.trim().to_string();
+		} else if trimmed.starts_with("Background:") {
+			in_background = true;
+			in_examples = false;
+		} else if let Some(rest) = trimmed
+			.strip_prefix("Scenario Outline:")
+			.or_else(|| trimmed.strip_prefix("Scenario Template:"))
+		{
+			in_background = false;
+			in_examples = false;
+			blocks.push(Block {
+				name: rest.trim().to_string(),
+				outline: true,
+				..Block::default()
+			});
+		} else if let Some(rest) = trimmed
+			.strip_prefix("Scenario:")
+			.or_else(|| trimmed.strip_prefix("Example:"))
+		{
+			in_background = false;
+			in_examples = false;
+			blocks.push(Block {
+				name: rest.trim().to_string(),
+				..Block::default()
+			});
+		} else if trimmed.starts_with("Examples:") || trimmed.starts_with("Scenarios:") {
+			if let Some(block) = blocks.last_mut() {
+				in_examples = true;
+				block.examples.push(Default::default());
+			}
+		} else if let Some((keyword, text)) = trimmed.split_once(' ') {
+			if ["Given", "When", "Then", "And", "But", "*"].contains(&keyword) {
+				current_steps(in_background, &mut background, &mut blocks).push(Step {
+					keyword: keyword.to_string(),
+					text: text.trim().to_string(),
+					table: Vec::new(),
+					docstring: None,
+				});
+			}
+		}
+	}
+
+	let mut scenarios = Vec::new();
+	for block in blocks {
+		let with_background = |steps: Vec<Step>| background.iter().cloned().chain(steps).collect();
+		if block.outline {
+			let rows = block
+				.examples
+				.iter()
+				.flat_map(|(header, rows)| rows.iter().map(move |row| (header, row)));
+			for (i, (header, row)) in rows.enumerate() {
+				scenarios.push(Scenario {
+					name: format!("{} (example {})", block.name, i + 1),
+					steps: with_background(
+						block
+							.steps
+							.iter()
+							.map(|step| step.substitute(header, row))
+							.collect(),
+					),
+				});
+			}
+		} else {
+			scenarios.push(Scenario {
+				name: block.name,
+				steps: with_background(block.steps),
+			});
+		}
+	}
+
+	Feature { name, scenarios }
+}
@@ -0,0 +1,71 @@
+//! Runs the Gherkin specification in `features/` and reports every scenario
+//! as passed, pending (a step has no definition yet) or failed.
+//!
+//! Positional arguments restrict the run to feature files whose name contains
+//! one of them, e.g. `cargo test --test features -- matrices tuples`.
+
+mod expr;
+mod gherkin;
+mod steps;
+
+use std::{env, fs, path::Path, process};
+
+use steps::Outcome;
+
+fn main() {
+	let filters: Vec<String> = env::args()
+		.skip(1)
+		.filter(|arg| !arg.starts_with('-'))
+		.collect();
+
+	let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("features");
+	let mut paths: Vec<_> = fs::read_dir(&dir)
+		.expect("features directory")
+		.map(|entry| entry.expect("features directory entry").path())
+		.filter(|path| path.extension().is_some_and(|ext| ext == "feature"))
+		.filter(|path| {
+			let name = path.file_name().unwrap().to_string_lossy();
+			filters.is_empty() || filters.iter().any(|filter| name.contains(filter.as_str()))
+		})
+		.collect();
+	paths.sort();
+
+	let (mut passed, mut pending, mut failed) = (0, 0, 0);
+	for path in paths {
+		let feature = gherkin::parse_file(&path)
+			.unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e));
+		println!(
+			"\n{} ({})",
+			feature.name,
+			path.file_name().unwrap().to_string_lossy()
+		);
+
+		for scenario in &feature.scenarios {
+			match steps::run(scenario) {
+				Outcome::Passed => {
+					passed += 1;
+					println!("  ok       {}", scenario.name);
+				}
+				Outcome::Pending(reason) => {
+					pending += 1;
+					println!("  pending  {}\n           {}", scenario.name, reason);
+				}
+				Outcome::Failed(reason) => {
+					failed += 1;
+					println!("  FAILED   {}\n           {}", scenario.name, reason);
+				}
+			}
+		}
+	}
+
+	println!(
+		"\n{} scenarios: {} passed, {} pending, {} failed\n",
+		passed + pending + failed,
+		passed,
+		pending,
+		failed
+	);
+	if failed > 0 {
+		process::exit(1);
+	}
+}
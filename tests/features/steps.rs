@@ -0,0 +1,282 @@
+//! Step definitions for tuples, colors, canvases and matrices
+
+use std::collections::HashMap;
+
+use crate::{
+	expr::{eval, failed, pending, Halt, Value},
+	gherkin::{Scenario, Step},
+};
+
+pub enum Outcome {
+	Passed,
+	Pending(String),
+	Failed(String),
+}
+
+/// Variables assigned by the steps of a single scenario
+#[derive(Default)]
+pub struct World {
+	vars: HashMap<String, Value>,
+}
+
+impl World {
+	fn eval(&self, src: &str) -> Result<Value, Halt> {
+		eval(src, &self.vars)
+	}
+
+	fn var_mut(&mut self, name: &str) -> Result<&mut Value, Halt> {
+		match self.vars.get_mut(name) {
+			Some(value) => Ok(value),
+			None => pending(format!("no variable `{}`", name)),
+		}
+	}
+}
+
+type StepFn = fn(&mut World, &[&str], &Step) -> Result<(), Halt>;
+
+/// Patterns are tried in order; each `{}` captures the text up to the next literal
+const STEPS: &[(&str, StepFn)] = &[
+	("the following {}x{} matrix {}:", given_sized_matrix),
+	("the following matrix {}:", given_matrix),
+	("write_pixel({}, {}, {}, {})", write_pixel),
+	("every pixel of {} is set to {}", fill_canvas),
+	("every pixel of {} is {}", every_pixel_is),
+	("lines {}-{} of {} are", lines_are),
+	("{} ends with a newline character", ends_with_newline),
+	("{} is a point", |w, args, _| {
+		is_tuple(w, args[0], true, true)
+	}),
+	("{} is not a point", |w, args, _| {
+		is_tuple(w, args[0], true, false)
+	}),
+	("{} is a vector", |w, args, _| {
+		is_tuple(w, args[0], false, true)
+	}),
+	("{} is not a vector", |w, args, _| {
+		is_tuple(w, args[0], false, false)
+	}),
+	("{} is invertible", |w, args, _| {
+		is_invertible(w, args[0], true)
+	}),
+	("{} is not invertible", |w, args, _| {
+		is_invertible(w, args[0], false)
+	}),
+	("{} is the following {}x{} matrix:", is_sized_matrix),
+	("{} is the following matrix:", |w, args, step| {
+		is_matrix(w, args[0], step)
+	}),
+	("{} ← {}", assign),
+	("{} = approximately {}", |w, args, _| {
+		equals(w, args[0], args[1], true)
+	}),
+	("{} != {}", |w, args, _| equals(w, args[0], args[1], false)),
+	("{} = {}", |w, args, _| equals(w, args[0], args[1], true)),
+];
+
+/// Match `text` against a pattern, returning the captured arguments
+fn captures<'a>(pattern: &str, text: &'a str) -> Option<Vec<&'a str>> {
+	let literals: Vec<&str> = pattern.split("{}").collect();
+	let mut rest = text.strip_prefix(literals[0])?;
+	let mut args = Vec::new();
+	for (i, literal) in literals.iter().enumerate().skip(1) {
+		let end = if i + 1 == literals.len() {
+			rest.strip_suffix(literal)?.len()
+		} else {
+			rest.find(literal)?
+		};
+		let arg = rest[..end].trim();
+		if arg.is_empty() {
+			return None;
+		}
+		args.push(arg);
+		rest = &rest[end + literal.len()..];
+	}
+	Some(args)
+}
+
+pub fn run(scenario: &Scenario) -> Outcome {
+	let mut world = World::default();
+	for step in &scenario.steps {
+		let result = STEPS
+			.iter()
+			.find_map(|(pattern, f)| captures(pattern, &step.text).map(|args| (f, args)))
+			.map_or_else(
+				|| pending("no step definition matches"),
+				|(f, args)| f(&mut world, &args, step),
+			);
+		match result {
+			Ok(()) => {}
+			Err(Halt::Pending(msg)) => {
+				return Outcome::Pending(format!("{} {}: {}", step.keyword, step.text, msg))
+			}
+			Err(Halt::Failed(msg)) => {
+				return Outcome::Failed(format!("{} {}: {}", step.keyword, step.text, msg))
+			}
+		}
+	}
+	Outcome::Passed
+}
+
+fn given_sized_matrix(world: &mut World, args: &[&str], step: &Step) -> Result<(), Halt> {
+	let matrix = sized_matrix(world, args[0], args[1], step)?;
+	world.vars.insert(args[2].to_string(), matrix);
+	Ok(())
+}
+
+fn given_matrix(world: &mut World, args: &[&str], step: &Step) -> Result<(), Halt> {
+	let matrix = Value::matrix_from_table(&step.table)?;
+	world.vars.insert(args[0].to_string(), matrix);
+	Ok(())
+}
+
+/// The step's table as a matrix, checking it has the stated size
+fn sized_matrix(world: &World, rows: &str, cols: &str, step: &Step) -> Result<Value, Halt> {
+	let (rows, cols) = (world.eval(rows)?.index()?, world.eval(cols)?.index()?);
+	let actual = step
+		.table
+		.first()
+		.map_or(0, |row| row.matches('|').count() - 1);
+	if step.table.len() != rows || actual != cols {
+		return failed(format!(
+			"table is {}x{}, not {}x{}",
+			step.table.len(),
+			actual,
+			rows,
+			cols
+		));
+	}
+	Value::matrix_from_table(&step.table)
+}
+
+fn write_pixel(world: &mut World, args: &[&str], _: &Step) -> Result<(), Halt> {
+	let x = world.eval(args[1])?.index()?;
+	let y = world.eval(args[2])?.index()?;
+	let color = world.eval(args[3])?.color()?;
+	match world.var_mut(args[0])? {
+		Value::Canvas(canvas) => canvas
+			.write_pixel(x, y, color)
+			.or_else(|e| failed(e.to_string())),
+		_ => failed(format!("`{}` is not a canvas", args[0])),
+	}
+}
+
+fn fill_canvas(world: &mut World, args: &[&str], _: &Step) -> Result<(), Halt> {
+	let color = world.eval(args[1])?.color()?;
+	match world.var_mut(args[0])? {
+		Value::Canvas(canvas) => {
+			canvas.pixels.iter_mut().for_each(|pixel| *pixel = color);
+			Ok(())
+		}
+		_ => failed(format!("`{}` is not a canvas", args[0])),
+	}
+}
+
+fn every_pixel_is(world: &mut World, args: &[&str], _: &Step) -> Result<(), Halt> {
+	let expected = world.eval(args[1])?;
+	match world.eval(args[0])? {
+		Value::Canvas(canvas) => {
+			for pixel in canvas.iter() {
+				if !Value::Color(*pixel).approx_eq(&expected)? {
+					return failed(format!("found pixel {}", pixel));
+				}
+			}
+			Ok(())
+		}
+		v => failed(format!("expected a canvas, found {}", v)),
+	}
+}
+
+fn lines_are(world: &mut World, args: &[&str], step: &Step) -> Result<(), Halt> {
+	let first = world.eval(args[0])?.index()?;
+	let last = world.eval(args[1])?.index()?;
+	if first == 0 || first > last {
+		return failed(format!("{}-{} is not a range of line numbers", first, last));
+	}
+	let value = world.eval(args[2])?;
+	let actual: Vec<&str> = value
+		.text()?
+		.lines()
+		.skip(first - 1)
+		.take(last - first + 1)
+		.collect();
+	let expected: Vec<&str> = step
+		.docstring
+		.as_deref()
+		.unwrap_or_default()
+		.lines()
+		.collect();
+	if actual == expected {
+		Ok(())
+	} else {
+		failed(format!("lines were {:?}", actual))
+	}
+}
+
+fn ends_with_newline(world: &mut World, args: &[&str], _: &Step) -> Result<(), Halt> {
+	if world.eval(args[0])?.text()?.ends_with('\n') {
+		Ok(())
+	} else {
+		failed("does not end with a newline")
+	}
+}
+
+fn is_tuple(world: &mut World, src: &str, point: bool, expected: bool) -> Result<(), Halt> {
+	let t = world.eval(src)?.tuple()?;
+	let actual = if point { t.is_point() } else { t.is_vector() };
+	if actual == expected {
+		Ok(())
+	} else {
+		failed(format!("{} has w = {}", t, t.w))
+	}
+}
+
+fn is_invertible(world: &mut World, src: &str, expected: bool) -> Result<(), Halt> {
+	let actual = match world.eval(src)? {
+		Value::Matrix2(m) => m.invert().is_some(),
+		Value::Matrix3(m) => m.invert().is_some(),
+		Value::Matrix4(m) => m.invert().is_some(),
+		v => return failed(format!("expected a matrix, found {}", v)),
+	};
+	if actual == expected {
+		Ok(())
+	} else {
+		failed("invertibility differs")
+	}
+}
+
+fn is_sized_matrix(world: &mut World, args: &[&str], step: &Step) -> Result<(), Halt> {
+	let expected = sized_matrix(world, args[1], args[2], step)?;
+	compare(world.eval(args[0])?, expected, true)
+}
+
+fn is_matrix(world: &mut World, src: &str, step: &Step) -> Result<(), Halt> {
+	compare(
+		world.eval(src)?,
+		Value::matrix_from_table(&step.table)?,
+		true,
+	)
+}
+
+fn assign(world: &mut World, args: &[&str], _: &Step) -> Result<(), Halt> {
+	let name = args[0];
+	if !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+		return pending(format!("cannot assign to `{}`", name));
+	}
+	let value = world.eval(args[1])?;
+	world.vars.insert(name.to_string(), value);
+	Ok(())
+}
+
+fn equals(world: &mut World, lhs: &str, rhs: &str, expected: bool) -> Result<(), Halt> {
+	compare(world.eval(lhs)?, world.eval(rhs)?, expected)
+}
+
+fn compare(actual: Value, expected: Value, equal: bool) -> Result<(), Halt> {
+	if actual.approx_eq(&expected)? == equal {
+		Ok(())
+	} else if equal {
+		failed(format!("expected {}, found {}", expected, actual))
+	} else {
+		failed(format!("both are {}", actual))
+	}
+}
@@ -0,0 +1,121 @@
+use raytrace::{
+	assert_approx_eq,
+	geometry::{Normal3, Point3, Vector3},
+	transformations::*,
+	tuple::Tuple,
+};
+use std::{convert::TryFrom, f64::consts::PI};
+
+#[test]
+fn subtracting_two_points() {
+	let p1 = Point3::new(3., 2., 1.);
+	let p2 = Point3::new(5., 6., 7.);
+
+	assert_eq!(p1 - p2, Vector3::new(-2., -4., -6.));
+}
+
+#[test]
+fn moving_a_point_by_a_vector() {
+	let p = Point3::new(3., 2., 1.);
+	let v = Vector3::new(5., 6., 7.);
+
+	assert_eq!(p + v, Point3::new(8., 8., 8.));
+	assert_eq!(p - v, Point3::new(-2., -4., -6.));
+}
+
+#[test]
+fn vector_arithmetic() {
+	let v1 = Vector3::new(3., 2., 1.);
+	let v2 = Vector3::new(5., 6., 7.);
+
+	assert_eq!(v1 - v2, Vector3::new(-2., -4., -6.));
+	assert_eq!(v1 + v2, Vector3::new(8., 8., 8.));
+	assert_eq!(-v1, Vector3::new(-3., -2., -1.));
+	assert_eq!(v1 * 2., Vector3::new(6., 4., 2.));
+	assert_eq!(v1 / 2., Vector3::new(1.5, 1., 0.5));
+}
+
+#[test]
+fn dot_and_cross_product() {
+	let a = Vector3::new(1., 2., 3.);
+	let b = Vector3::new(2., 3., 4.);
+
+	assert_eq!(a.dot(&b), 20.);
+	assert_eq!(a.cross(&b), Vector3::new(-1., 2., -1.));
+	assert_eq!(b.cross(&a), Vector3::new(1., -2., 1.));
+}
+
+#[test]
+fn normalizing_vector() {
+	let v = Vector3::new(1., 2., 3.);
+
+	assert_eq!(v.normalize().magnitude(), 1.);
+	assert_eq!(
+		Vector3::new(4., 0., 0.).normalize(),
+		Vector3::new(1., 0., 0.)
+	);
+}
+
+#[test]
+fn reflecting_vector_off_slanted_surface() {
+	let v = Vector3::new(0., -1., 0.);
+	let pv = 2f64.sqrt() / 2.;
+	let n = Normal3::new(pv, pv, 0.);
+
+	assert_approx_eq!(v.reflect(&n), Vector3::new(1., 0., 0.));
+}
+
+#[test]
+fn translation_moves_points_but_not_vectors() {
+	let transform = translation(5., -3., 2.);
+
+	assert_eq!(
+		transform * Point3::new(-3., 4., 5.),
+		Point3::new(2., 1., 7.)
+	);
+	assert_eq!(
+		transform * Vector3::new(-3., 4., 5.),
+		Vector3::new(-3., 4., 5.)
+	);
+}
+
+#[test]
+fn normals_are_transformed_by_inverse_transpose() {
+	let s = 2f64.sqrt() / 2.;
+	let n = Normal3::new(0., s, -s);
+
+	assert_approx_eq!(translation(0., 1., 0.).transform_normal(n).unwrap(), n);
+	assert_approx_eq!(
+		scaling(1., 0.5, 1.).transform_normal(n).unwrap(),
+		Normal3::new(0., 0.89443, -0.44721)
+	);
+	assert_approx_eq!(
+		rotation_x(PI / 2.).transform_normal(n).unwrap(),
+		Normal3::from(rotation_x(PI / 2.) * Vector3::from(n))
+	);
+}
+
+#[test]
+fn normals_are_transformed_by_tiny_scales() {
+	let s = 2f64.sqrt() / 2.;
+	let n = Normal3::new(0., s, -s);
+
+	// Normals are renormalized, so only the ratio between the axes matters
+	assert_approx_eq!(
+		scaling(1e-6, 0.5e-6, 1e-6).transform_normal(n).unwrap(),
+		Normal3::new(0., 0.89443, -0.44721)
+	);
+}
+
+#[test]
+fn converting_to_and_from_tuples() {
+	let p = Point3::new(1., 2., 3.);
+	let v = Vector3::new(1., 2., 3.);
+
+	assert_eq!(Tuple::from(p), Tuple::point(1., 2., 3.));
+	assert_eq!(Tuple::from(v), Tuple::vector(1., 2., 3.));
+	assert_eq!(Point3::try_from(Tuple::point(1., 2., 3.)), Ok(p));
+	assert_eq!(Vector3::try_from(Tuple::vector(1., 2., 3.)), Ok(v));
+	assert!(Point3::try_from(Tuple::vector(1., 2., 3.)).is_err());
+	assert!(Normal3::try_from(Tuple::point(1., 2., 3.)).is_err());
+}
@@ -0,0 +1,183 @@
+use raytrace::{
+	assert_approx_eq,
+	canvas::Canvas,
+	color::{Color, BLACK, WHITE},
+	error::Error,
+	utils::Tolerance,
+};
+
+/// Colors well outside 0..1, including a negative channel
+fn bright(width: usize, height: usize) -> Canvas {
+	let mut c = Canvas::new(width, height);
+	for y in 0..height {
+		for x in 0..width {
+			let t = (x + y * width) as f64;
+			c[(y, x)] = Color::new(t * 0.75, 40. / (t + 1.), (t % 3.) * 1000.);
+		}
+	}
+	c[(0, 0)] = Color::new(-2., 0.5, 65504.);
+	c
+}
+
+#[test]
+fn pfm_header_and_row_order() {
+	let mut c = Canvas::new(2, 2);
+	c[(1, 0)] = Color::new(1.5, 0., 0.);
+	let pfm = c.as_pfm();
+	let header = b"PF\n2 2\n-1.0\n";
+
+	assert!(pfm.starts_with(header));
+	assert_eq!(pfm.len(), header.len() + 2 * 2 * 3 * 4);
+	// The bottom row comes first
+	assert_eq!(pfm[header.len()..header.len() + 4], 1.5f32.to_le_bytes());
+}
+
+#[test]
+fn pfm_round_trips_unclamped() {
+	let c = bright(5, 3);
+	let read = Canvas::read_pfm(&c.as_pfm()[..]).unwrap();
+
+	assert_eq!((read.width, read.height), (5, 3));
+	for (actual, expected) in read.iter().zip(c.iter()) {
+		assert_eq!(actual.r, f64::from(expected.r as f32));
+		assert_eq!(actual.g, f64::from(expected.g as f32));
+		assert_eq!(actual.b, f64::from(expected.b as f32));
+	}
+	assert_eq!(read[(0, 0)], Color::new(-2., 0.5, 65504.));
+}
+
+#[test]
+fn read_big_endian_grayscale_pfm() {
+	let mut data = b"Pf\n2 1\n4.0\n".to_vec();
+	data.extend_from_slice(&2.5f32.to_be_bytes());
+	data.extend_from_slice(&0f32.to_be_bytes());
+	let c = Canvas::from_pfm(&data).unwrap();
+
+	assert_eq!(c.pixels, vec![Color::new(2.5, 2.5, 2.5), BLACK]);
+}
+
+#[test]
+fn hdr_round_trips_within_precision() {
+	// Wide enough for run-length encoded scanlines, and too narrow for them
+	for &(width, height) in &[(40, 6), (3, 4)] {
+		let mut c = bright(width, height);
+		for x in 10..width {
+			c[(2, x)] = Color::new(3., 3., 3.);
+		}
+		let hdr = c.as_hdr();
+		let read = Canvas::read_hdr(&hdr[..]).unwrap();
+
+		assert_eq!((read.width, read.height), (width, height));
+		// The negative channel becomes the smallest step below the brightest one
+		assert!((0. ..=65504. / 256.).contains(&read[(0, 0)].r));
+		for (actual, expected) in read.iter().zip(c.iter()).skip(1) {
+			// Each channel keeps 8 bits relative to the brightest one
+			let brightest = expected.r.max(expected.g).max(expected.b);
+			let tolerance = Tolerance::Absolute(brightest / 128.);
+			assert_approx_eq!(actual.r, expected.r, tolerance = tolerance);
+			assert_approx_eq!(actual.g, expected.g, tolerance = tolerance);
+			assert_approx_eq!(actual.b, expected.b, tolerance = tolerance);
+		}
+	}
+}
+
+#[test]
+fn hdr_compresses_flat_scanlines() {
+	let c = Canvas::new(200, 10);
+	let hdr = c.as_hdr();
+
+	assert!(hdr.starts_with(b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 10 +X 200\n"));
+	assert!(hdr.len() < 200 * 10);
+	assert!(Canvas::from_hdr(&hdr)
+		.unwrap()
+		.pixels
+		.iter()
+		.all(|p| *p == BLACK));
+}
+
+#[test]
+fn read_bottom_up_hdr_with_old_style_runs() {
+	let mut data = b"#?RGBE\nEXPOSURE=1.0\n\n+Y 2 +X 3\n".to_vec();
+	// Bottom row: one white pixel repeated twice more
+	data.extend_from_slice(&[128, 128, 128, 129, 1, 1, 1, 2]);
+	// Top row: flat pixels
+	data.extend_from_slice(&[0, 0, 0, 0, 128, 0, 0, 130, 0, 0, 0, 0]);
+	let c = Canvas::from_hdr(&data).unwrap();
+
+	let half = 128.5 / 256. * 2.;
+	assert_eq!(
+		c.pixels,
+		vec![
+			BLACK,
+			Color::new(
+				128.5 * 2f64.powi(-6),
+				0.5 * 2f64.powi(-6),
+				0.5 * 2f64.powi(-6)
+			),
+			BLACK,
+			Color::new(half, half, half),
+			Color::new(half, half, half),
+			Color::new(half, half, half),
+		]
+	);
+}
+
+#[test]
+fn read_rejects_malformed_hdr_and_pfm() {
+	let hdr: &[&[u8]] = &[
+		b"RADIANCE\n\n-Y 1 +X 1\n\x00\x00\x00\x00",
+		b"#?RADIANCE\nFORMAT=32-bit_rle_xyze\n\n-Y 1 +X 1\n\x00\x00\x00\x00",
+		b"#?RADIANCE\n\n+X 1 -Y 1\n\x00\x00\x00\x00",
+		b"#?RADIANCE\n\n-Y 2 +X 1\n\x00\x00\x00\x00",
+		b"#?RADIANCE\n\n-Y 1 +X 8\n\x02\x02\x00\x08\x89\x00",
+		b"#?RADIANCE\n-Y 1 +X 1\n",
+		b"#?RADIANCE\n\n-Y 18446744073709551615 +X 0\n",
+		b"#?RADIANCE\n\n-Y 0 +X 3\n",
+	];
+	for data in hdr {
+		assert!(matches!(
+			Canvas::from_hdr(data),
+			Err(Error::InvalidImage(_))
+		));
+	}
+
+	let pfm: &[&[u8]] = &[
+		b"PG\n1 1\n-1.0\n\x00\x00\x00\x00",
+		b"Pf\n1 1\n0\n\x00\x00\x00\x00",
+		b"Pf\n1 1\nscale\n\x00\x00\x00\x00",
+		b"Pf\n2 1\n-1.0\n\x00\x00\x00\x00",
+		b"PF\n1 1\n-1.0",
+		b"PF\n0 18446744073709551615\n-1.0\n",
+		b"Pf\n3 0\n-1.0\n",
+	];
+	for data in pfm {
+		assert!(matches!(
+			Canvas::from_pfm(data),
+			Err(Error::InvalidImage(_))
+		));
+	}
+}
+
+#[test]
+fn save_and_load_keep_dynamic_range() {
+	let mut c = Canvas::new(9, 2);
+	c[(1, 4)] = Color::new(12., 0.5, 300.);
+	c[(0, 8)] = WHITE;
+
+	for extension in &["hdr", "pfm"] {
+		let path =
+			std::env::temp_dir().join(format!("raytrace-hdr-{}.{}", std::process::id(), extension));
+		c.save(&path).unwrap();
+		let loaded = Canvas::load(&path);
+		std::fs::remove_file(&path).unwrap();
+
+		let loaded = loaded.unwrap();
+		assert_eq!((loaded.width, loaded.height), (9, 2));
+		assert_approx_eq!(
+			loaded[(1, 4)],
+			Color::new(12., 0.5, 300.),
+			tolerance = Tolerance::Absolute(2.)
+		);
+		assert_approx_eq!(loaded[(0, 8)], WHITE, tolerance = Tolerance::Absolute(0.01));
+	}
+}
@@ -1,9 +1,10 @@
-use raytrace::{assert_approx_eq, matrix::*};
-use raytrace::{matrix, tuple::Tuple};
+use raytrace::{assert_approx_eq, error::Error, matrix::*, utils::Tolerance};
+use raytrace::{matrix, transformations::*, tuple::Tuple};
+use std::convert::TryFrom;
 
 #[test]
 fn create_matrix4x4() {
-	let mat = matrix![ M4, M4 =>
+	let mat = matrix![ 4, 4 =>
 		 1.0,  2.0,  3.0,  4.0;
 		 5.5,  6.5,  7.5,  8.5;
 		 9.0, 10.0, 11.0, 12.0;
@@ -21,7 +22,7 @@ fn create_matrix4x4() {
 
 #[test]
 fn create_matrix3x3() {
-	let mat = matrix![ M3, M3 =>
+	let mat = matrix![ 3, 3 =>
 		-3,  5,  0;
 		 1, -2, -7;
 		 0,  1,  1;
@@ -34,7 +35,7 @@ fn create_matrix3x3() {
 
 #[test]
 fn create_matrix2x2() {
-	let mat = matrix![ M2, M2 =>
+	let mat = matrix![ 2, 2 =>
 		-3,  5;
 		 1, -2;
 	];
@@ -45,21 +46,29 @@ fn create_matrix2x2() {
 	assert_eq!(mat[(1, 1)], -2);
 }
 
+#[test]
+fn matrix4x4_is_copy() {
+	let a: Matrix4x4<f64> = Matrix::identity();
+	let b = a;
+
+	assert_eq!(a * b, a);
+}
+
 #[test]
 fn matrix_equality() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		1,2,3,4;
 		5,6,7,8;
 		9,8,7,6;
 		5,4,3,2;
 	];
-	let b = matrix![ M4, M4 =>
+	let b = matrix![ 4, 4 =>
 		1,2,3,4;
 		5,6,7,8;
 		9,8,7,6;
 		5,4,3,2;
 	];
-	let c = matrix![ M4, M4 =>
+	let c = matrix![ 4, 4 =>
 		2,3,4,5;
 		6,7,8,9;
 		8,7,6,5;
@@ -72,19 +81,19 @@ fn matrix_equality() {
 
 #[test]
 fn multiply_matrix_by_matrix() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		1, 2, 3, 4;
 		5, 6, 7, 8;
 		9, 8, 7, 6;
 		5, 4, 3, 2;
 	];
-	let b = matrix![ M4, M4 =>
+	let b = matrix![ 4, 4 =>
 		-2, 1, 2,  3;
 		 3, 2, 1, -1;
 		 4, 3, 6,  5;
 		 1, 2, 7,  8;
 	];
-	let c = matrix![ M4, M4 =>
+	let c = matrix![ 4, 4 =>
 		20, 22,  50,  48;
 		44, 54, 114, 108;
 		40, 58, 110, 102;
@@ -96,7 +105,7 @@ fn multiply_matrix_by_matrix() {
 
 #[test]
 fn multiply_matrix_by_tuple() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		1., 2., 3., 4.;
 		2., 4., 4., 2.;
 		8., 6., 4., 1.;
@@ -109,15 +118,51 @@ fn multiply_matrix_by_tuple() {
 	assert_eq!(expected, actual.to_tuple());
 }
 
+#[test]
+fn integer_matrix_to_tuple() {
+	let a = matrix![ 4, 1 =>
+		1;
+		-2;
+		3;
+		1;
+	];
+
+	assert_eq!(
+		a.to_tuple(),
+		Tuple {
+			x: 1,
+			y: -2,
+			z: 3,
+			w: 1
+		}
+	);
+}
+
+#[test]
+#[allow(clippy::op_ref)] // covers the by-reference impl as well
+fn multiply_matrix_by_tuple_directly() {
+	let a = matrix![ 4, 4 =>
+		1., 2., 3., 4.;
+		2., 4., 4., 2.;
+		8., 6., 4., 1.;
+		0., 0., 0., 1f64;
+	];
+	let b = Tuple::new(1., 2., 3., 1.);
+	let expected = Tuple::new(18., 24., 33., 1.);
+
+	assert_eq!(&a * b, expected);
+	assert_eq!(a * b, expected);
+}
+
 #[test]
 fn multiply_matrix_by_identity() {
-	let a: Matrix4x4<i32> = matrix![ M4, M4 =>
+	let a: Matrix4x4<i32> = matrix![ 4, 4 =>
 		0, 1, 2, 4;
 		1, 2, 4, 8;
 		2, 4, 8, 16;
 		4, 8, 16, 32;
 	];
-	let actual = a.clone() * Matrix::identity();
+	let actual = a * Matrix::identity();
 
 	assert_eq!(a, actual);
 }
@@ -132,13 +177,13 @@ fn multiply_identity_by_tuple() {
 
 #[test]
 fn transpose_matrix() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		0, 9, 3, 0;
 		9, 8, 0, 8;
 		1, 8, 5, 3;
 		0, 0, 5, 8;
 	];
-	let expected = matrix![ M4, M4 =>
+	let expected = matrix![ 4, 4 =>
 		0, 9, 1, 0;
 		9, 8, 8, 0;
 		3, 0, 5, 5;
@@ -151,14 +196,14 @@ fn transpose_matrix() {
 #[test]
 fn transpose_identity() {
 	let a: Matrix4x4<i32> = Matrix::identity();
-	let expected = a.clone();
+	let expected = a;
 
 	assert_eq!(a.transpose(), expected);
 }
 
 #[test]
 fn det_2x2() {
-	let a = matrix![ M2, M2 =>
+	let a = matrix![ 2, 2 =>
 		1, 5;
 		-3, 2;
 	];
@@ -168,13 +213,13 @@ fn det_2x2() {
 
 #[test]
 fn submatrix() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		-6, 1,  1, 6;
 		-8, 5,  8, 6;
 		-1, 0,  8, 2;
 		-7, 1, -1, 1;
 	];
-	let expected = matrix! [ M3, M3 =>
+	let expected = matrix! [ 3, 3 =>
 		-6,  1, 6;
 		-8,  8, 6;
 		-7, -1, 1;
@@ -185,19 +230,19 @@ fn submatrix() {
 
 #[test]
 fn minor() {
-	let a = matrix![ M3, M3 =>
+	let a = matrix![ 3, 3 =>
 		3,  5,  0;
 		2, -1, -7;
 		6, -1,  5;
 	];
 
-	assert_eq!(a.submatrix(1, 0).det(), 25);
+	assert_eq!(a.submatrix::<2, 2>(1, 0).det(), 25);
 	assert_eq!(a.minor(1, 0), 25);
 }
 
 #[test]
 fn cofactor() {
-	let a = matrix![ M3, M3 =>
+	let a = matrix![ 3, 3 =>
 		3,  5,  0;
 		2, -1, -7;
 		6, -1,  5;
@@ -209,9 +254,21 @@ fn cofactor() {
 	assert_eq!(a.cofactor(1, 0), -25);
 }
 
+#[test]
+fn minor_and_cofactor_2x2() {
+	let a = matrix![ 2, 2 =>
+		1,  5;
+		-3, 2;
+	];
+
+	assert_eq!(a.minor(0, 1), -3);
+	assert_eq!(a.cofactor(0, 1), 3);
+	assert_eq!(a.cofactor(1, 1), 1);
+}
+
 #[test]
 fn det_3x3() {
-	let a = matrix![ M3, M3 =>
+	let a = matrix![ 3, 3 =>
 		 1, 2,  6;
 		-5, 8, -4;
 		 2, 6,  4;
@@ -225,7 +282,7 @@ fn det_3x3() {
 
 #[test]
 fn det_4x4() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		-2, -8,  3,  5;
 		-3,  1,  7,  3;
 		 1,  2, -9,  6;
@@ -239,16 +296,46 @@ fn det_4x4() {
 	assert_eq!(a.det(), -4071);
 }
 
+#[test]
+fn det_6x6() {
+	let a = matrix![ 6, 6 =>
+		2, 0, 0, 0, 0, 0;
+		0, 0, 3, 0, 0, 0;
+		0, 1, 0, 0, 0, 0;
+		0, 0, 0, 4, 0, 0;
+		0, 0, 0, 0, 5, 1;
+		0, 0, 0, 0, 0, 1;
+	];
+
+	assert_eq!(a.det(), -120);
+}
+
+#[test]
+fn multiply_non_square_matrices() {
+	let a = matrix![ 2, 3 =>
+		1, 2, 3;
+		4, 5, 6;
+	];
+	let b = matrix![ 3, 1 =>
+		1;
+		0;
+		-1;
+	];
+
+	assert_eq!(a * b, matrix![ 2, 1 => -2; -2; ]);
+	assert_eq!(a.transpose(), matrix![ 3, 2 => 1, 4; 2, 5; 3, 6; ]);
+}
+
 #[test]
 fn invertability() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		6,  4, 4,  4;
 		5,  5, 7,  6;
 		4, -9, 3, -7;
 		9,  1, 7, -6;
 	];
 
-	let b = matrix![ M4, M4 =>
+	let b = matrix![ 4, 4 =>
 		-4,  2, -2, -3;
 		 9,  6,  2,  6;
 		 0, -5,  1, -5;
@@ -260,11 +347,26 @@ fn invertability() {
 
 	assert_eq!(b.det(), 0);
 	assert!(b.invert().is_none());
+	assert_eq!(b.try_invert(), Err(Error::NonInvertible));
+}
+
+#[test]
+fn try_from_vec() {
+	let m = Matrix2x2::try_from(vec![1, 2, 3, 4]).unwrap();
+	assert_eq!(m, matrix![ 2, 2 => 1, 2; 3, 4; ]);
+
+	assert_eq!(
+		Matrix::<i32, 2, 3>::try_from(vec![1, 2, 3, 4]),
+		Err(Error::DimensionMismatch {
+			expected: 6,
+			actual: 4
+		})
+	);
 }
 
 #[test]
 fn invert_matrix() {
-	let a: Matrix4x4<f32> = matrix![ M4, M4 =>
+	let a: Matrix4x4<f32> = matrix![ 4, 4 =>
 		-5.,  2.,  6., -8.;
 		 1., -5.,  1.,  8.;
 		 7.,  7., -6., -7.;
@@ -272,7 +374,7 @@ fn invert_matrix() {
 	];
 	assert_approx_eq!(
 		a.invert().unwrap(),
-		&matrix![ M4, M4 =>
+		&matrix![ 4, 4 =>
 			0.21805,  0.45113,  0.24060, -0.04511;
 		   -0.80827, -1.45677, -0.44361,  0.52068;
 		   -0.07895, -0.22368, -0.05263,  0.19737;
@@ -280,7 +382,7 @@ fn invert_matrix() {
 		]
 	);
 
-	let b = matrix![ M4, M4 =>
+	let b = matrix![ 4, 4 =>
 		 8., -5.,  9.,  2.;
 		 7.,  5.,  6.,  1.;
 		-6.,  0.,  9.,  6.;
@@ -288,7 +390,7 @@ fn invert_matrix() {
 	];
 	assert_approx_eq!(
 		b.invert().unwrap(),
-		&matrix![ M4, M4 =>
+		&matrix![ 4, 4 =>
 			-0.15385, -0.15385, -0.28205, -0.53846;
 			-0.07692,  0.12308,  0.02564,  0.03077;
 			 0.35897,  0.35897,  0.43590,  0.92308;
@@ -296,7 +398,7 @@ fn invert_matrix() {
 		]
 	);
 
-	let c = matrix![ M4, M4 =>
+	let c = matrix![ 4, 4 =>
 		 9.,  3.,  0.,  9.;
 		-5., -2., -6., -3.;
 		-4.,  9.,  6.,  4.;
@@ -304,7 +406,7 @@ fn invert_matrix() {
 	];
 	assert_approx_eq!(
 		c.invert().unwrap(),
-		&matrix![ M4, M4 =>
+		&matrix![ 4, 4 =>
 			-0.04074, -0.07778,  0.14444, -0.22222;
 			-0.07778,  0.03333,  0.36667, -0.33333;
 			-0.02901, -0.14630, -0.10926,  0.12963;
@@ -315,19 +417,325 @@ fn invert_matrix() {
 
 #[test]
 fn inverse_multiplication() {
-	let a = matrix![ M4, M4 =>
+	let a = matrix![ 4, 4 =>
 		 3., -9.,  7.,  3.;
 		 3., -8.,  2., -9.;
 		-4.,  4.,  4.,  1.;
 		-6.,  5., -1.,  1.;
 	];
-	let b = matrix![ M4, M4 =>
+	let b = matrix![ 4, 4 =>
 		8.,  2., 2., 2.;
 		3., -1., 7., 0.;
 		7.,  0., 5., 4.;
 		6., -2., 0., 5.;
 	];
 
-	let c = &a * &b;
+	let c = a * b;
 	assert_approx_eq!(c * b.invert().unwrap(), &a);
 }
+
+#[test]
+fn lu_det() {
+	let a = matrix![ 4, 4 =>
+		-2., -8.,  3.,  5.;
+		-3.,  1.,  7.,  3.;
+		 1.,  2., -9.,  6.;
+		-6.,  7.,  7., -9.;
+	];
+
+	assert_approx_eq!(a.lu().unwrap().det(), -4071.);
+}
+
+#[test]
+fn lu_invert() {
+	let a = matrix![ 4, 4 =>
+		 8., -5.,  9.,  2.;
+		 7.,  5.,  6.,  1.;
+		-6.,  0.,  9.,  6.;
+		-3.,  0., -9., -4.;
+	];
+	assert_approx_eq!(
+		&a.lu().unwrap().invert(),
+		&matrix![ 4, 4 =>
+			-0.15385, -0.15385, -0.28205, -0.53846;
+			-0.07692,  0.12308,  0.02564,  0.03077;
+			 0.35897,  0.35897,  0.43590,  0.92308;
+			-0.69231, -0.69231, -0.76923, -1.92308;
+		]
+	);
+}
+
+#[test]
+fn lu_solve() {
+	let a = matrix![ 3, 3 =>
+		0., 2., 1.;
+		1., 1., 1.;
+		2., 1., 0.;
+	];
+	let b = matrix![ 3, 1 =>
+		7.;
+		6.;
+		4.;
+	];
+
+	assert_approx_eq!(&a.solve(&b).unwrap(), &matrix![ 3, 1 => 1.; 2.; 3.; ]);
+}
+
+#[test]
+fn lu_singular() {
+	let a = matrix![ 4, 4 =>
+		-4.,  2., -2., -3.;
+		 9.,  6.,  2.,  6.;
+		 0., -5.,  1., -5.;
+		 0.,  0.,  0.,  0.;
+	];
+
+	assert!(a.lu().is_none());
+	assert!(a.solve(&matrix![ 4, 1 => 1.; 1.; 1.; 1.; ]).is_none());
+}
+
+#[test]
+fn lu_condition_number() {
+	let identity: Matrix4x4<f64> = Matrix::identity();
+	assert_approx_eq!(identity.lu().unwrap().cond(), 1.);
+
+	let a = matrix![ 2, 2 =>
+		1., 1.;
+		1., 1.0001;
+	];
+	assert!(a.lu().unwrap().cond() > 1e4);
+}
+
+#[test]
+fn lu_rejects_nan() {
+	let a = matrix![ 3, 3 =>
+		1., 0., 0.;
+		0., f64::NAN, 0.;
+		0., 0., 1.;
+	];
+
+	assert!(a.lu().is_none());
+	assert!(a.invert().is_none());
+}
+
+#[test]
+fn float_invert_uses_lu() {
+	// The determinant is about 1e-15, not zero, but the rows agree to working precision
+	let a = matrix![ 2, 2 =>
+		1., 2.;
+		2., 4. + 4e-15;
+	];
+	assert_eq!(a.det(), 0.);
+	assert!(a.invert().is_none());
+
+	let b = matrix![ 3, 3 =>
+		0., 2., 1.;
+		1., 1., 1.;
+		2., 1., 0.;
+	];
+	assert_eq!(b.invert(), b.lu().map(|lu| lu.invert()));
+}
+
+#[test]
+fn float_det_pivots_on_largest_element() {
+	// Eliminating with the tiny leading element as the pivot cancels the
+	// rest of the matrix and yields 0 instead of 2 - 3e-20
+	let a = matrix![ 3, 3 =>
+		1e-20, 1., 1.;
+		1.,    1., 2.;
+		1.,    2., 1.;
+	];
+
+	assert_approx_eq!(a.det(), 2.);
+	assert_approx_eq!(a.cofactor(0, 0), -3.);
+	assert_approx_eq!(a.minor(1, 0), -1.);
+}
+
+#[test]
+fn invert_fast_matches_generic_inverse() {
+	let matrices = [
+		matrix![ 4, 4 =>
+			-5.,  2.,  6., -8.;
+			 1., -5.,  1.,  8.;
+			 7.,  7., -6., -7.;
+			 1., -3.,  7.,  4.;
+		],
+		matrix![ 4, 4 =>
+			 8., -5.,  9.,  2.;
+			 7.,  5.,  6.,  1.;
+			-6.,  0.,  9.,  6.;
+			-3.,  0., -9., -4.;
+		],
+		matrix![ 4, 4 =>
+			 9.,  3.,  0.,  9.;
+			-5., -2., -6., -3.;
+			-4.,  9.,  6.,  4.;
+			-7.,  6.,  6.,  2.;
+		],
+		matrix![ 4, 4 =>
+			 3., -9.,  7.,  3.;
+			 3., -8.,  2., -9.;
+			-4.,  4.,  4.,  1.;
+			-6.,  5., -1.,  1.;
+		],
+	];
+
+	for m in matrices.iter() {
+		assert!(!m.is_affine());
+		assert_approx_eq!(&m.invert_fast().unwrap(), &m.invert().unwrap());
+	}
+}
+
+#[test]
+fn invert_fast_singular() {
+	let a = matrix![ 4, 4 =>
+		-4.,  2., -2., -3.;
+		 9.,  6.,  2.,  6.;
+		 0., -5.,  1., -5.;
+		 0.,  0.,  0.,  0.;
+	];
+
+	assert!(a.invert_fast().is_none());
+}
+
+#[test]
+fn invert_fast_tiny_matrices() {
+	let tiny = scaling(1e-6, 1e-6, 1e-6);
+	let expected = scaling(1e6, 1e6, 1e6);
+	assert_approx_eq!(
+		&tiny.invert_affine().unwrap(),
+		&expected,
+		tolerance = Tolerance::Relative(1e-12)
+	);
+	assert_approx_eq!(
+		&tiny.invert_fast().unwrap(),
+		&expected,
+		tolerance = Tolerance::Relative(1e-12)
+	);
+
+	// Not affine, so this takes the general closed form
+	let a = matrix![ 4, 4 =>
+		-5.,  2.,  6., -8.;
+		 1., -5.,  1.,  8.;
+		 7.,  7., -6., -7.;
+		 1., -3.,  7.,  4.;
+	] * 1e-6;
+	assert_approx_eq!(&(a * a.invert_fast().unwrap()), &Matrix::identity());
+}
+
+#[test]
+fn invert_affine_matches_generic_inverse() {
+	let a = translation(10., 5., 7.)
+		* rotation_x(0.5)
+		* scaling(2., 3., 4.)
+		* shearing(1., 0., 0.5, 0., 0., 2.);
+
+	assert!(a.is_affine());
+	assert_approx_eq!(&a.invert_affine().unwrap(), &a.invert().unwrap());
+	assert_approx_eq!(&a.invert_fast().unwrap(), &a.invert().unwrap());
+	assert!(scaling(0., 1., 1.).invert_affine().is_none());
+}
+
+#[test]
+fn element_wise_arithmetic() {
+	let a = matrix![ 2, 2 =>
+		1, 2;
+		3, 4;
+	];
+	let b = matrix![ 2, 2 =>
+		5, 6;
+		7, 8;
+	];
+
+	assert_eq!(a + b, matrix![ 2, 2 => 6, 8; 10, 12; ]);
+	assert_eq!(b - a, matrix![ 2, 2 => 4, 4; 4, 4; ]);
+	assert_eq!(-a, matrix![ 2, 2 => -1, -2; -3, -4; ]);
+	assert_eq!(a * 3, matrix![ 2, 2 => 3, 6; 9, 12; ]);
+	assert_eq!(b / 2, matrix![ 2, 2 => 2, 3; 3, 4; ]);
+	assert_eq!(a.hadamard(&b), matrix![ 2, 2 => 5, 12; 21, 32; ]);
+}
+
+#[test]
+fn element_wise_assignment() {
+	let mut a = matrix![ 2, 2 =>
+		1., 2.;
+		3., 4.;
+	];
+	a += Matrix::identity();
+	assert_eq!(a, matrix![ 2, 2 => 2., 2.; 3., 5.; ]);
+	a -= Matrix::identity();
+	assert_eq!(a, matrix![ 2, 2 => 1., 2.; 3., 4.; ]);
+	a *= 2.;
+	assert_eq!(a, matrix![ 2, 2 => 2., 4.; 6., 8.; ]);
+	a /= 4.;
+	assert_eq!(a, matrix![ 2, 2 => 0.5, 1.; 1.5, 2.; ]);
+}
+
+#[test]
+fn averaging_matrices() {
+	let transforms = [translation(1., 2., 3.), translation(3., 4., 5.)];
+	let average = transforms.iter().sum::<Matrix4x4<f64>>() / transforms.len() as f64;
+
+	assert_approx_eq!(&average, &translation(2., 3., 4.));
+	assert_eq!(
+		transforms.iter().copied().sum::<Matrix4x4<f64>>(),
+		transforms[0] + transforms[1]
+	);
+}
+
+#[test]
+fn trace() {
+	let a = matrix![ 3, 3 =>
+		1, 2, 3;
+		4, 5, 6;
+		7, 8, 9;
+	];
+
+	assert_eq!(a.trace(), 15);
+	assert_eq!(Matrix4x4::<f64>::identity().trace(), 4.);
+}
+
+#[test]
+fn parse_pipe_table() {
+	let m: Matrix4x4<f64> = "
+		|  1   |  2   |  3   |  4   |
+		|  5.5 |  6.5 |  7.5 |  8.5 |
+		|  9   | 10   | 11   | 12   |
+		| 13.5 | 14.5 | 15.5 | 16.5 |
+	"
+	.parse()
+	.unwrap();
+
+	assert_eq!(m[(0, 3)], 4.);
+	assert_eq!(m[(1, 2)], 7.5);
+	assert_eq!(m[(3, 2)], 15.5);
+}
+
+#[test]
+fn parse_whitespace_rows() {
+	let m: Matrix<f64, 2, 3> = "-3 5 0\n 1 -2 -7".parse().unwrap();
+	assert_eq!(m, matrix![ 2, 3 => -3., 5., 0.; 1., -2., -7.; ]);
+}
+
+#[test]
+fn parse_rejects_wrong_shape() {
+	assert!("1 2\n3 4".parse::<Matrix3x3<f64>>().is_err());
+	assert!("| 1 | 2 |\n| 3 |".parse::<Matrix2x2<f64>>().is_err());
+	assert!("| 1 | x |\n| 3 | 4 |".parse::<Matrix2x2<f64>>().is_err());
+}
+
+#[test]
+fn display_round_trips() {
+	let m = matrix![ 3, 3 =>
+		 1., -2.5,  3.;
+		10.,  0.,  -0.125;
+		 7.,  8.,   9.;
+	];
+	let s = m.to_string();
+
+	assert_eq!(
+		s,
+		"|  1 | -2.5 |      3 |\n| 10 |    0 | -0.125 |\n|  7 |    8 |      9 |"
+	);
+	assert_eq!(s.parse::<Matrix3x3<f64>>().unwrap(), m);
+}
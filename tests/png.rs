@@ -0,0 +1,111 @@
+use raytrace::{
+	canvas::{BitDepth, Canvas},
+	color::Color,
+	error::Error,
+};
+
+/// Split a PNG into its chunks, checking the signature and every CRC with crc32fast
+fn chunks(png: &[u8]) -> Vec<([u8; 4], Vec<u8>)> {
+	assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+	let mut chunks = Vec::new();
+	let mut rest = &png[8..];
+	while !rest.is_empty() {
+		let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+		let kind = [rest[4], rest[5], rest[6], rest[7]];
+		let crc =
+			u32::from_be_bytes([rest[8 + len], rest[9 + len], rest[10 + len], rest[11 + len]]);
+		assert_eq!(crc32fast::hash(&rest[4..8 + len]), crc);
+		chunks.push((kind, rest[8..8 + len].to_vec()));
+		rest = &rest[12 + len..];
+	}
+	chunks
+}
+
+fn gradient(width: usize, height: usize) -> Canvas {
+	let mut c = Canvas::new(width, height);
+	for y in 0..height {
+		for x in 0..width {
+			c[(y, x)] = Color::new(
+				x as f64 / width as f64,
+				y as f64 / height as f64,
+				((x * y) % 7) as f64 / 7.,
+			);
+		}
+	}
+	c
+}
+
+#[test]
+fn png_header_and_chunks() {
+	let png = Canvas::new(5, 3).as_png(BitDepth::Eight).unwrap();
+	let chunks = chunks(&png);
+	let kinds: Vec<_> = chunks.iter().map(|(kind, _)| kind).collect();
+
+	assert_eq!(kinds, vec![b"IHDR", b"IDAT", b"IEND"]);
+	assert_eq!(chunks[0].1, vec![0, 0, 0, 5, 0, 0, 0, 3, 8, 2, 0, 0, 0]);
+	assert!(chunks[2].1.is_empty());
+}
+
+#[test]
+fn png_pixels_round_trip() {
+	let c = gradient(64, 48);
+
+	for &depth in &[BitDepth::Eight, BitDepth::Sixteen] {
+		let png = c.as_png(depth).unwrap();
+		let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+		let mut pixels = vec![0; reader.output_buffer_size()];
+		let info = reader.next_frame(&mut pixels).unwrap();
+
+		assert_eq!((info.width, info.height), (64, 48));
+		assert_eq!(info.color_type, png::ColorType::Rgb);
+		let bits = if depth == BitDepth::Eight {
+			png::BitDepth::Eight
+		} else {
+			png::BitDepth::Sixteen
+		};
+		assert_eq!(info.bit_depth, bits);
+
+		// PNG samples are big-endian like raw PPM, so the rasters must agree
+		let ppm = c.as_p6(depth);
+		assert!(ppm.ends_with(&pixels[..info.buffer_size()]));
+	}
+}
+
+#[test]
+fn png_image_data_is_a_valid_zlib_stream() {
+	let c = gradient(40, 30);
+	let png = c.as_png(BitDepth::Eight).unwrap();
+	let idat = &chunks(&png)[1].1;
+
+	// miniz_oxide checks the Adler-32 trailer against what it decompressed
+	let raw = miniz_oxide::inflate::decompress_to_vec_zlib(idat).unwrap();
+	assert_eq!(raw.len(), 30 * (1 + 40 * 3));
+
+	let mut corrupted = idat.clone();
+	*corrupted.last_mut().unwrap() ^= 1;
+	assert!(miniz_oxide::inflate::decompress_to_vec_zlib(&corrupted).is_err());
+}
+
+#[test]
+fn png_compresses_smooth_images() {
+	let c = gradient(300, 200);
+	let png = c.as_png(BitDepth::Eight).unwrap();
+
+	assert!(png.len() * 10 < c.as_p6(BitDepth::Eight).len());
+}
+
+#[test]
+fn empty_canvas_is_rejected() {
+	let mut buf = Vec::new();
+	let err = Canvas::new(0, 4)
+		.write_png(&mut buf, BitDepth::Eight)
+		.unwrap_err();
+
+	assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+	for &(width, height) in &[(0, 4), (4, 0), (0, 0)] {
+		assert!(matches!(
+			Canvas::new(width, height).as_png(BitDepth::Eight),
+			Err(Error::Io(_))
+		));
+	}
+}
@@ -0,0 +1,102 @@
+use raytrace::{
+	assert_approx_eq, matrix::Matrix, quaternion::Quaternion, transformations::*, tuple::Tuple,
+};
+use std::f64::consts::PI;
+
+#[test]
+fn axis_angle_matches_rotation_matrices() {
+	let angles = [PI / 4., PI / 2., 2., -0.3];
+	for &r in angles.iter() {
+		let qx = Quaternion::from_axis_angle(Tuple::vector(1., 0., 0.), r);
+		let qy = Quaternion::from_axis_angle(Tuple::vector(0., 1., 0.), r);
+		let qz = Quaternion::from_axis_angle(Tuple::vector(0., 0., 1.), r);
+
+		assert_approx_eq!(&qx.to_matrix(), &rotation_x(r));
+		assert_approx_eq!(&qy.to_matrix(), &rotation_y(r));
+		assert_approx_eq!(&qz.to_matrix(), &rotation_z(r));
+	}
+}
+
+#[test]
+fn axis_angle_round_trip() {
+	let axis = Tuple::vector(1., 2., 3.).normalize();
+	let q = Quaternion::from_axis_angle(axis, 1.2);
+	let (actual_axis, actual_angle) = q.to_axis_angle();
+
+	assert_eq!(actual_axis, axis);
+	assert_approx_eq!(actual_angle, 1.2);
+
+	let (_, angle) = Quaternion::identity().to_axis_angle();
+	assert_approx_eq!(angle, 0.);
+}
+
+#[test]
+fn matrix_round_trip() {
+	let matrices = [
+		rotation_x(PI / 2.),
+		rotation_y(PI) * rotation_x(0.1),
+		rotation_z(3.) * rotation_y(-1.) * rotation_x(2.5),
+		Matrix::identity(),
+	];
+	for m in matrices.iter() {
+		let q = Quaternion::from_matrix(m);
+		assert_approx_eq!(&Matrix::from(q), m);
+	}
+}
+
+#[test]
+fn composition_matches_matrix_product() {
+	let a = Quaternion::from_axis_angle(Tuple::vector(1., 0., 0.), PI / 2.);
+	let b = Quaternion::from_axis_angle(Tuple::vector(0., 1., 0.), PI / 3.);
+
+	assert_approx_eq!(
+		&(a * b).to_matrix(),
+		&(rotation_x(PI / 2.) * rotation_y(PI / 3.))
+	);
+}
+
+#[test]
+fn rotating_a_point() {
+	let q = Quaternion::from_axis_angle(Tuple::vector(0., 0., 1.), PI / 2.);
+
+	assert_approx_eq!(
+		q.rotate(Tuple::point(0., 1., 0.)),
+		Tuple::point(-1., 0., 0.)
+	);
+	assert_approx_eq!(
+		q.conjugate().rotate(Tuple::point(-1., 0., 0.)),
+		Tuple::point(0., 1., 0.)
+	);
+}
+
+#[test]
+fn normalization() {
+	let q = Quaternion::new(2., 0., 0., 0.).normalize();
+
+	assert_approx_eq!(q, Quaternion::identity());
+	assert_approx_eq!(Quaternion::new(1., 2., 3., 4.).normalize().magnitude(), 1.);
+}
+
+#[test]
+fn slerp_interpolates_angle_linearly() {
+	let axis = Tuple::vector(0., 1., 0.);
+	let a = Quaternion::identity();
+	let b = Quaternion::from_axis_angle(axis, PI / 2.);
+
+	assert_approx_eq!(a.slerp(&b, 0.), a);
+	assert_approx_eq!(a.slerp(&b, 1.), b);
+	assert_approx_eq!(a.slerp(&b, 0.5), Quaternion::from_axis_angle(axis, PI / 4.));
+	assert_approx_eq!(
+		a.slerp(&b, 0.25),
+		Quaternion::from_axis_angle(axis, PI / 8.)
+	);
+}
+
+#[test]
+fn slerp_takes_the_shortest_path() {
+	let axis = Tuple::vector(0., 0., 1.);
+	let a = Quaternion::from_axis_angle(axis, 0.1);
+	let b = -Quaternion::from_axis_angle(axis, 0.3);
+
+	assert_approx_eq!(&a.slerp(&b, 0.5).to_matrix(), &rotation_z(0.2));
+}
@@ -0,0 +1,43 @@
+use raytrace::{
+	geometry::{Point3, Vector3},
+	ray::Ray,
+	transformations::*,
+};
+
+#[test]
+fn creating_and_querying_a_ray() {
+	let origin = Point3::new(1., 2., 3.);
+	let direction = Vector3::new(4., 5., 6.);
+	let r = Ray::new(origin, direction);
+
+	assert_eq!(r.origin, origin);
+	assert_eq!(r.direction, direction);
+}
+
+#[test]
+fn computing_point_from_distance() {
+	let r = Ray::new(Point3::new(2., 3., 4.), Vector3::new(1., 0., 0.));
+
+	assert_eq!(r.position(0.), Point3::new(2., 3., 4.));
+	assert_eq!(r.position(1.), Point3::new(3., 3., 4.));
+	assert_eq!(r.position(-1.), Point3::new(1., 3., 4.));
+	assert_eq!(r.position(2.5), Point3::new(4.5, 3., 4.));
+}
+
+#[test]
+fn translating_a_ray() {
+	let r = Ray::new(Point3::new(1., 2., 3.), Vector3::new(0., 1., 0.));
+	let r2 = r.transform(&translation(3., 4., 5.));
+
+	assert_eq!(r2.origin, Point3::new(4., 6., 8.));
+	assert_eq!(r2.direction, Vector3::new(0., 1., 0.));
+}
+
+#[test]
+fn scaling_a_ray() {
+	let r = Ray::new(Point3::new(1., 2., 3.), Vector3::new(0., 1., 0.));
+	let r2 = r.transform(&scaling(2., 3., 4.));
+
+	assert_eq!(r2.origin, Point3::new(2., 6., 12.));
+	assert_eq!(r2.direction, Vector3::new(0., 3., 0.));
+}
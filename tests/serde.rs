@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+use raytrace::{
+	canvas::Canvas,
+	color::{Color, RED},
+	matrix,
+	matrix::{Matrix2x2, Matrix4x4},
+	transformations::*,
+	tuple::Tuple,
+};
+
+#[test]
+fn tuple_round_trip() {
+	let t = Tuple::point(1., 2.5, -3.);
+	let json = serde_json::to_string(&t).unwrap();
+
+	assert_eq!(json, r#"{"x":1.0,"y":2.5,"z":-3.0,"w":1.0}"#);
+	assert_eq!(serde_json::from_str::<Tuple>(&json).unwrap(), t);
+}
+
+#[test]
+fn color_round_trip() {
+	let c = Color::new(0.25f32, 0.5, 1.);
+	let json = serde_json::to_string(&c).unwrap();
+
+	assert_eq!(json, r#"{"r":0.25,"g":0.5,"b":1.0}"#);
+	assert_eq!(serde_json::from_str::<Color<f32>>(&json).unwrap(), c);
+}
+
+#[test]
+fn matrix_round_trip() {
+	let m = matrix![ 2, 2 =>
+		1, 2;
+		3, 4;
+	];
+	let json = serde_json::to_string(&m).unwrap();
+	assert_eq!(json, "[1,2,3,4]");
+	assert_eq!(serde_json::from_str::<Matrix2x2<i32>>(&json).unwrap(), m);
+
+	let t = translation(1., 2., 3.) * rotation_y(0.5);
+	let json = serde_json::to_string(&t).unwrap();
+	assert_eq!(serde_json::from_str::<Matrix4x4<f64>>(&json).unwrap(), t);
+}
+
+#[test]
+fn matrix_rejects_wrong_element_count() {
+	let err = serde_json::from_str::<Matrix2x2<i32>>("[1,2,3]").unwrap_err();
+
+	assert!(err.to_string().contains("4 elements for a 2x2 matrix"));
+	assert!(serde_json::from_str::<Matrix2x2<i32>>("[1,2,3,4,5]").is_err());
+}
+
+#[test]
+fn canvas_round_trip() {
+	let mut c = Canvas::new(2, 1);
+	c[(0, 1)] = RED;
+	let json = serde_json::to_string(&c).unwrap();
+	let actual: Canvas = serde_json::from_str(&json).unwrap();
+
+	assert_eq!(actual.width, 2);
+	assert_eq!(actual.height, 1);
+	assert_eq!(actual.pixels, c.pixels);
+}
+
+#[test]
+fn canvas_rejects_wrong_pixel_count() {
+	let json = r#"{"width":2,"height":2,"pixels":[{"r":0.0,"g":0.0,"b":0.0}]}"#;
+
+	assert!(serde_json::from_str::<Canvas>(json).is_err());
+}
+
+#[test]
+fn canvas_rejects_overflowing_dimensions() {
+	let json = format!(
+		r#"{{"width":{},"height":2,"pixels":[]}}"#,
+		usize::MAX / 2 + 1
+	);
+
+	match serde_json::from_str::<Canvas>(&json) {
+		Err(err) => assert!(err.to_string().contains("too large")),
+		Ok(_) => panic!("overflowing dimensions were accepted"),
+	}
+}
@@ -0,0 +1,166 @@
+use raytrace::{
+	assert_approx_eq,
+	color::Color,
+	matrix,
+	simd::{scalar, Lanes},
+	tuple::Tuple,
+	utils::ApproxEq,
+};
+
+const A: [f64; 4] = [1.5, -2.25, 3.125, 1.];
+const B: [f64; 4] = [-0.5, 4., 0.75, 0.];
+
+fn lanes_eq<T: Lanes + ApproxEq>(actual: [T; 4], expected: [T; 4]) -> bool {
+	actual
+		.iter()
+		.zip(expected.iter())
+		.all(|(a, e)| a.approx_eq(*e))
+}
+
+fn to_f32(a: [f64; 4]) -> [f32; 4] {
+	[a[0] as f32, a[1] as f32, a[2] as f32, a[3] as f32]
+}
+
+#[test]
+fn lane_arithmetic_matches_scalar() {
+	let sum = [A[0] + B[0], A[1] + B[1], A[2] + B[2], A[3] + B[3]];
+	let difference = [A[0] - B[0], A[1] - B[1], A[2] - B[2], A[3] - B[3]];
+	let product = [A[0] * B[0], A[1] * B[1], A[2] * B[2], A[3] * B[3]];
+	let scaled = [A[0] * 3., A[1] * 3., A[2] * 3., A[3] * 3.];
+
+	assert!(lanes_eq(f64::add4(A, B), sum));
+	assert!(lanes_eq(f64::sub4(A, B), difference));
+	assert!(lanes_eq(f64::mul4(A, B), product));
+	assert!(lanes_eq(f64::scale4(A, 3.), scaled));
+
+	assert!(lanes_eq(f32::add4(to_f32(A), to_f32(B)), to_f32(sum)));
+	assert!(lanes_eq(
+		f32::sub4(to_f32(A), to_f32(B)),
+		to_f32(difference)
+	));
+	assert!(lanes_eq(f32::mul4(to_f32(A), to_f32(B)), to_f32(product)));
+	assert!(lanes_eq(f32::scale4(to_f32(A), 3.), to_f32(scaled)));
+}
+
+#[test]
+fn dot_and_cross_match_scalar() {
+	let dot = A[0] * B[0] + A[1] * B[1] + A[2] * B[2] + A[3] * B[3];
+	let cross = [
+		A[1] * B[2] - A[2] * B[1],
+		A[2] * B[0] - A[0] * B[2],
+		A[0] * B[1] - A[1] * B[0],
+		0.,
+	];
+
+	assert_approx_eq!(f64::dot4(A, B), dot);
+	assert_approx_eq!(f32::dot4(to_f32(A), to_f32(B)), dot as f32);
+	assert!(lanes_eq(f64::cross3(A, B), cross));
+	assert!(lanes_eq(f32::cross3(to_f32(A), to_f32(B)), to_f32(cross)));
+}
+
+#[test]
+fn tuple_color_and_matrix_products() {
+	let a = Tuple::from(A);
+	let b = Tuple::from(B);
+	let m = matrix![ 4, 4 =>
+		1., 2., 3., 4.;
+		2., 4., 4., 2.;
+		8., 6., 4., 1.;
+		0., 0., 0., 1.;
+	];
+
+	assert_eq!(a + b, Tuple::new(1., 1.75, 3.875, 1.));
+	assert_eq!(a - b, Tuple::new(2., -6.25, 2.375, 1.));
+	assert_eq!(a * 2., Tuple::new(3., -4.5, 6.25, 2.));
+	assert_approx_eq!(
+		a.magnitude(),
+		(1.5f64.powi(2) + 2.25f64.powi(2) + 3.125f64.powi(2)).sqrt()
+	);
+	assert_eq!(
+		m * Tuple::new(1., 2., 3., 1.),
+		Tuple::new(18., 24., 33., 1.)
+	);
+
+	let c = Color::new(0.9f32, 0.6, 0.75) + Color::new(0.7, 0.1, 0.25);
+	assert_approx_eq!(c, Color::new(1.6, 0.7, 1.));
+}
+
+/// Deterministic inputs spanning signs and magnitudes, plus a few special values
+fn inputs() -> Vec<[f64; 4]> {
+	let mut state = 0x2545_f491_4f6c_dd1du64;
+	let mut next = move || {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		let unit = (state >> 11) as f64 / (1u64 << 53) as f64;
+		(unit - 0.5) * 10f64.powi((state % 13) as i32 - 6)
+	};
+	let mut inputs: Vec<[f64; 4]> = (0..200).map(|_| [next(), next(), next(), next()]).collect();
+	inputs.push([0., -0., f64::INFINITY, 1.]);
+	inputs.push([f64::MAX, f64::MIN_POSITIVE, -1e300, 0.]);
+	inputs
+}
+
+/// Equal bit for bit, treating any two NaNs as equal
+fn same<T: Lanes>(actual: [T; 4], expected: [T; 4]) -> bool {
+	actual
+		.iter()
+		.zip(expected.iter())
+		.all(|(a, e)| a == e || (a.is_nan() && e.is_nan()))
+}
+
+#[test]
+fn vectorized_lanes_equal_scalar_lanes() {
+	let inputs = inputs();
+	for (&a, &b) in inputs.iter().zip(inputs.iter().rev()) {
+		let s = b[0];
+		assert!(same(f64::add4(a, b), scalar::add4(a, b)));
+		assert!(same(f64::sub4(a, b), scalar::sub4(a, b)));
+		assert!(same(f64::mul4(a, b), scalar::mul4(a, b)));
+		assert!(same(f64::scale4(a, s), scalar::scale4(a, s)));
+		assert!(same(f64::cross3(a, b), scalar::cross3(a, b)));
+
+		let (a, b, s) = (to_f32(a), to_f32(b), s as f32);
+		assert!(same(f32::add4(a, b), scalar::add4(a, b)));
+		assert!(same(f32::sub4(a, b), scalar::sub4(a, b)));
+		assert!(same(f32::mul4(a, b), scalar::mul4(a, b)));
+		assert!(same(f32::scale4(a, s), scalar::scale4(a, s)));
+		assert!(same(f32::cross3(a, b), scalar::cross3(a, b)));
+	}
+}
+
+#[test]
+fn vectorized_dot_products_equal_scalar_dot_products() {
+	// The vectorized sums are added in a different order, so allow for rounding
+	let close = |actual: f64, expected: f64, magnitude: f64, epsilon: f64| {
+		(actual - expected).abs() <= 4. * epsilon * magnitude
+	};
+	// Only finite inputs, infinities would turn the error bound into NaN
+	let inputs = &inputs()[..200];
+	for (&a, &b) in inputs.iter().zip(inputs.iter().rev()) {
+		let magnitude: f64 = scalar::mul4(a, b).iter().map(|x| x.abs()).sum();
+		assert!(close(
+			f64::dot4(a, b),
+			scalar::dot4(a, b),
+			magnitude,
+			f64::EPSILON
+		));
+
+		let (a32, b32) = (to_f32(a), to_f32(b));
+		let magnitude32: f32 = scalar::mul4(a32, b32).iter().map(|x| x.abs()).sum();
+		assert!(close(
+			f64::from(f32::dot4(a32, b32)),
+			f64::from(scalar::dot4(a32, b32)),
+			f64::from(magnitude32),
+			f64::from(f32::EPSILON)
+		));
+
+		let m = [a, b, scalar::add4(a, b), scalar::sub4(a, b)];
+		let product = f64::mul_mat4(&m, b);
+		let expected = scalar::mul_mat4(&m, b);
+		for (row, (&actual, &expected)) in m.iter().zip(product.iter().zip(expected.iter())) {
+			let magnitude: f64 = scalar::mul4(*row, b).iter().map(|x| x.abs()).sum();
+			assert!(close(actual, expected, magnitude, f64::EPSILON));
+		}
+	}
+}
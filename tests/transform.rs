@@ -0,0 +1,82 @@
+use raytrace::{
+	assert_approx_eq,
+	geometry::{Normal3, Point3, Vector3},
+	ray::Ray,
+	transform::Transform,
+	transformations::*,
+	utils::Tolerance,
+};
+use std::f64::consts::PI;
+
+#[test]
+fn caches_inverse_and_inverse_transpose() {
+	let m = translation(1., 2., 3.) * scaling(2., 3., 4.);
+	let t = Transform::new(m).unwrap();
+	let inverse = m.invert().unwrap();
+
+	assert_eq!(t.matrix(), &m);
+	assert_approx_eq!(t.inverse(), &inverse);
+	assert_approx_eq!(t.inverse_transpose(), &inverse.transpose());
+}
+
+#[test]
+fn singular_matrix_has_no_transform() {
+	assert!(Transform::new(scaling(1., 0., 1.)).is_none());
+}
+
+#[test]
+fn tiny_scale_has_a_transform() {
+	let t = Transform::new(scaling(1e-6, 2e-6, 4e-6)).unwrap();
+	let p = Point3::new(3., -2., 5.);
+
+	assert_approx_eq!(
+		t.point(p),
+		Point3::new(3e-6, -4e-6, 2e-5),
+		tolerance = Tolerance::Relative(1e-12)
+	);
+	assert_approx_eq!(t.invert().point(t.point(p)), p);
+	assert_approx_eq!(t.normal(Normal3::new(0., 1., 0.)), Normal3::new(0., 1., 0.));
+}
+
+#[test]
+fn transforming_points_vectors_and_normals() {
+	let t = Transform::new(translation(5., -3., 2.)).unwrap();
+	let s = 2f64.sqrt() / 2.;
+
+	assert_approx_eq!(t.point(Point3::new(-3., 4., 5.)), Point3::new(2., 1., 7.));
+	assert_approx_eq!(
+		t.vector(Vector3::new(-3., 4., 5.)),
+		Vector3::new(-3., 4., 5.)
+	);
+	assert_approx_eq!(t.normal(Normal3::new(0., s, -s)), Normal3::new(0., s, -s));
+
+	let t = Transform::new(scaling(1., 0.5, 1.)).unwrap();
+	assert_approx_eq!(
+		t.normal(Normal3::new(0., s, -s)),
+		Normal3::new(0., 0.89443, -0.44721)
+	);
+}
+
+#[test]
+fn transforming_rays() {
+	let t = Transform::new(scaling(2., 3., 4.)).unwrap();
+	let r = Ray::new(Point3::new(1., 2., 3.), Vector3::new(0., 1., 0.));
+
+	let r2 = t.ray(&r);
+	assert_approx_eq!(r2.origin, Point3::new(2., 6., 12.));
+	assert_approx_eq!(r2.direction, Vector3::new(0., 3., 0.));
+	assert_approx_eq!(t.inverse_ray(&r2), r);
+}
+
+#[test]
+fn composing_transforms() {
+	let a = Transform::new(rotation_x(PI / 2.)).unwrap();
+	let b = Transform::new(scaling(5., 5., 5.)).unwrap();
+	let c = Transform::new(translation(10., 5., 7.)).unwrap();
+	let t = c * b * a;
+	let p = Point3::new(1., 0., 1.);
+
+	assert_approx_eq!(t.point(p), Point3::new(15., 0., 7.));
+	assert_approx_eq!(t.inverse(), &t.matrix().invert().unwrap());
+	assert_approx_eq!(t.invert().point(t.point(p)), p);
+}
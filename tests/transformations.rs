@@ -0,0 +1,252 @@
+// Several tests multiply through `&Matrix4x4` on purpose to cover that impl
+#![allow(clippy::op_ref)]
+
+use raytrace::{
+	assert_approx_eq, matrix,
+	matrix::{Matrix, Matrix4x4},
+	transformations::*,
+	tuple::Tuple,
+};
+use std::f64::consts::PI;
+
+#[test]
+fn multiply_by_translation() {
+	let transform = translation(5., -3., 2.);
+	let p = Tuple::point(-3., 4., 5.);
+
+	assert_eq!(&transform * p, Tuple::point(2., 1., 7.));
+}
+
+#[test]
+fn multiply_by_inverse_translation() {
+	let transform = translation(5., -3., 2.);
+	let inv = transform.invert().unwrap();
+	let p = Tuple::point(-3., 4., 5.);
+
+	assert_eq!(&inv * p, Tuple::point(-8., 7., 3.));
+}
+
+#[test]
+fn translation_does_not_affect_vectors() {
+	let transform = translation(5., -3., 2.);
+	let v = Tuple::vector(-3., 4., 5.);
+
+	assert_eq!(&transform * v, v);
+}
+
+#[test]
+fn scaling_applied_to_point() {
+	let transform = scaling(2., 3., 4.);
+	let p = Tuple::point(-4., 6., 8.);
+
+	assert_eq!(&transform * p, Tuple::point(-8., 18., 32.));
+}
+
+#[test]
+fn scaling_applied_to_vector() {
+	let transform = scaling(2., 3., 4.);
+	let v = Tuple::vector(-4., 6., 8.);
+
+	assert_eq!(&transform * v, Tuple::vector(-8., 18., 32.));
+}
+
+#[test]
+fn multiply_by_inverse_scaling() {
+	let transform = scaling(2., 3., 4.);
+	let inv = transform.invert().unwrap();
+	let v = Tuple::vector(-4., 6., 8.);
+
+	assert_eq!(&inv * v, Tuple::vector(-2., 2., 2.));
+}
+
+#[test]
+fn reflection_is_negative_scaling() {
+	let transform = scaling(-1., 1., 1.);
+	let p = Tuple::point(2., 3., 4.);
+
+	assert_eq!(&transform * p, Tuple::point(-2., 3., 4.));
+}
+
+#[test]
+fn rotate_around_x() {
+	let p = Tuple::point(0., 1., 0.);
+	let half_quarter = rotation_x(PI / 4.);
+	let full_quarter = rotation_x(PI / 2.);
+	let s = 2f64.sqrt() / 2.;
+
+	assert_approx_eq!(&half_quarter * p, Tuple::point(0., s, s));
+	assert_approx_eq!(&full_quarter * p, Tuple::point(0., 0., 1.));
+}
+
+#[test]
+fn inverse_x_rotation() {
+	let p = Tuple::point(0., 1., 0.);
+	let half_quarter = rotation_x(PI / 4.);
+	let inv = half_quarter.invert().unwrap();
+	let s = 2f64.sqrt() / 2.;
+
+	assert_approx_eq!(&inv * p, Tuple::point(0., s, -s));
+}
+
+#[test]
+fn rotate_around_y() {
+	let p = Tuple::point(0., 0., 1.);
+	let half_quarter = rotation_y(PI / 4.);
+	let full_quarter = rotation_y(PI / 2.);
+	let s = 2f64.sqrt() / 2.;
+
+	assert_approx_eq!(&half_quarter * p, Tuple::point(s, 0., s));
+	assert_approx_eq!(&full_quarter * p, Tuple::point(1., 0., 0.));
+}
+
+#[test]
+fn rotate_around_z() {
+	let p = Tuple::point(0., 1., 0.);
+	let half_quarter = rotation_z(PI / 4.);
+	let full_quarter = rotation_z(PI / 2.);
+	let s = 2f64.sqrt() / 2.;
+
+	assert_approx_eq!(&half_quarter * p, Tuple::point(-s, s, 0.));
+	assert_approx_eq!(&full_quarter * p, Tuple::point(-1., 0., 0.));
+}
+
+#[test]
+fn shearing_moves_each_component() {
+	let p = Tuple::point(2., 3., 4.);
+
+	assert_eq!(
+		shearing(1., 0., 0., 0., 0., 0.) * p,
+		Tuple::point(5., 3., 4.)
+	);
+	assert_eq!(
+		shearing(0., 1., 0., 0., 0., 0.) * p,
+		Tuple::point(6., 3., 4.)
+	);
+	assert_eq!(
+		shearing(0., 0., 1., 0., 0., 0.) * p,
+		Tuple::point(2., 5., 4.)
+	);
+	assert_eq!(
+		shearing(0., 0., 0., 1., 0., 0.) * p,
+		Tuple::point(2., 7., 4.)
+	);
+	assert_eq!(
+		shearing(0., 0., 0., 0., 1., 0.) * p,
+		Tuple::point(2., 3., 6.)
+	);
+	assert_eq!(
+		shearing(0., 0., 0., 0., 0., 1.) * p,
+		Tuple::point(2., 3., 7.)
+	);
+}
+
+#[test]
+fn transformations_applied_in_sequence() {
+	let p = Tuple::point(1., 0., 1.);
+	let a = rotation_x(PI / 2.);
+	let b = scaling(5., 5., 5.);
+	let c = translation(10., 5., 7.);
+
+	let p2 = &a * p;
+	assert_approx_eq!(p2, Tuple::point(1., -1., 0.));
+	let p3 = &b * p2;
+	assert_approx_eq!(p3, Tuple::point(5., -5., 0.));
+	let p4 = &c * p3;
+	assert_approx_eq!(p4, Tuple::point(15., 0., 7.));
+}
+
+#[test]
+fn chained_transformations_in_reverse_order() {
+	let p = Tuple::point(1., 0., 1.);
+	let a = rotation_x(PI / 2.);
+	let b = scaling(5., 5., 5.);
+	let c = translation(10., 5., 7.);
+
+	let t = c * b * a;
+	assert_approx_eq!(&t * p, Tuple::point(15., 0., 7.));
+}
+
+#[test]
+fn fluent_transformations_in_reading_order() {
+	let p = Tuple::point(1., 0., 1.);
+	let t = Matrix::identity()
+		.rotate_x(PI / 2.)
+		.scale(5., 5., 5.)
+		.translate(10., 5., 7.);
+
+	assert_approx_eq!(
+		&t,
+		&(translation(10., 5., 7.) * scaling(5., 5., 5.) * rotation_x(PI / 2.))
+	);
+	assert_approx_eq!(t * p, Tuple::point(15., 0., 7.));
+}
+
+#[test]
+fn fluent_rotations_and_shearing() {
+	let p = Tuple::point(0., 1., 0.);
+	let t = Matrix::identity()
+		.rotate_z(PI / 2.)
+		.rotate_y(PI / 2.)
+		.shear(0., 1., 0., 0., 0., 0.);
+
+	assert_approx_eq!(t * p, Tuple::point(1., 0., 1.));
+}
+
+#[test]
+fn view_transform_default_orientation() {
+	let from = Tuple::point(0., 0., 0.);
+	let to = Tuple::point(0., 0., -1.);
+	let up = Tuple::vector(0., 1., 0.);
+
+	assert_approx_eq!(&view_transform(from, to, up), &Matrix::identity());
+}
+
+#[test]
+fn view_transform_looking_in_positive_z() {
+	let from = Tuple::point(0., 0., 0.);
+	let to = Tuple::point(0., 0., 1.);
+	let up = Tuple::vector(0., 1., 0.);
+
+	assert_approx_eq!(&view_transform(from, to, up), &scaling(-1., 1., -1.));
+}
+
+#[test]
+fn view_transform_moves_the_world() {
+	let from = Tuple::point(0., 0., 8.);
+	let to = Tuple::point(0., 0., 0.);
+	let up = Tuple::vector(0., 1., 0.);
+
+	assert_approx_eq!(&view_transform(from, to, up), &translation(0., 0., -8.));
+}
+
+#[test]
+fn arbitrary_view_transform() {
+	let from = Tuple::point(1., 3., 2.);
+	let to = Tuple::point(4., -2., 8.);
+	let up = Tuple::vector(1., 1., 0.);
+
+	assert_approx_eq!(
+		&view_transform(from, to, up),
+		&matrix![ 4, 4 =>
+			-0.50709, 0.50709,  0.67612, -2.36643;
+			 0.76772, 0.60609,  0.12122, -2.82843;
+			-0.35857, 0.59761, -0.71714,  0.00000;
+			 0.00000, 0.00000,  0.00000,  1.00000;
+		]
+	);
+}
+
+#[test]
+fn single_precision_transform() {
+	let transform: Matrix4x4<f32> = matrix![ 4, 4 =>
+		1., 0., 0., 5.;
+		0., 1., 0., -3.;
+		0., 0., 1., 2.;
+		0., 0., 0., 1.;
+	];
+
+	assert_eq!(
+		transform * Tuple::point(-3f32, 4., 5.),
+		Tuple::point(2., 1., 7.)
+	);
+}
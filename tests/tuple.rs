@@ -1,4 +1,4 @@
-use raytrace::tuple::Tuple;
+use raytrace::{assert_approx_eq, error::Error, tuple::Tuple};
 
 #[test]
 fn tuple_is_point() {
@@ -303,3 +303,62 @@ fn cross_product_of_two_vectors() {
 
 // 	assert_eq!(r, Tuple::vector(1., 0., 0.));
 // }
+
+#[test]
+fn single_precision_tuples() {
+	let a: Tuple<f32> = Tuple::vector(1., 2., 3.);
+	let b: Tuple<f32> = Tuple::vector(2., 3., 4.);
+
+	assert_eq!(a.cross(&b), Tuple::vector(-1., 2., -1.));
+	assert_eq!(a.dot(&b), 20.);
+	assert_approx_eq!(a.normalize().magnitude(), 1.);
+	assert!(Tuple::<f32>::point(1., 2., 3.).is_point());
+}
+
+#[test]
+fn single_precision_matches_double_precision() {
+	let a64 = Tuple::vector(0.3, -1.7, 2.9);
+	let a32 = Tuple::vector(0.3f32, -1.7, 2.9);
+	let n64 = a64.normalize();
+	let n32 = a32.normalize();
+
+	assert!((n64.x - f64::from(n32.x)).abs() < 1e-6);
+	assert!((n64.y - f64::from(n32.y)).abs() < 1e-6);
+	assert!((n64.z - f64::from(n32.z)).abs() < 1e-6);
+}
+
+#[test]
+fn parse_tuples() {
+	assert_eq!("point(1, 2, 3)".parse(), Ok(Tuple::point(1., 2., 3.)));
+	assert_eq!(
+		" vector(4,-4, 3.5) ".parse(),
+		Ok(Tuple::vector(4., -4., 3.5))
+	);
+	assert_eq!(
+		"tuple(4.3, -4.2, 3.1, 1.0)".parse(),
+		Ok(Tuple::new(4.3, -4.2, 3.1, 1.))
+	);
+	assert_eq!(
+		"point(1, 2)".parse::<Tuple>().unwrap_err(),
+		Error::Parse(
+			"expected point(x, y, z), vector(x, y, z) or tuple(x, y, z, w), found `point(1, 2)`"
+				.into()
+		)
+	);
+	assert!("vector(1, a, 3)".parse::<Tuple>().is_err());
+	assert!("point 1 2 3".parse::<Tuple>().is_err());
+}
+
+#[test]
+fn display_tuples_round_trips() {
+	for t in [
+		Tuple::point(1., -2.5, 0.1),
+		Tuple::vector(0., 1e-7, 3.),
+		Tuple::new(1., 2., 3., 0.5),
+	]
+	.iter()
+	{
+		assert_eq!(t.to_string().parse(), Ok(*t));
+	}
+	assert_eq!(Tuple::point(1., 2., 3.).to_string(), "point(1, 2, 3)");
+}